@@ -0,0 +1,148 @@
+//! Controls how the parallel solvers run: on rayon's global pool, on a pool
+//! with a fixed number of threads, or fully sequentially on the calling
+//! thread. Every parallel solver goes through [`map_reduce`] or [`map_sum`],
+//! so the mode applies to all of them.
+
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use serde::Serialize;
+use std::cell::Cell;
+use std::iter::Sum;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Execution {
+    /// rayon's global pool
+    #[default]
+    Parallel,
+    /// A dedicated pool with this many threads
+    Threads(usize),
+    /// No rayon at all, everything runs on the calling thread
+    Sequential,
+}
+
+thread_local! {
+    static SEQUENTIAL: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Restores the previous mode even if the solver panics
+struct SequentialGuard(bool);
+
+impl Drop for SequentialGuard {
+    fn drop(&mut self) {
+        SEQUENTIAL.with(|sequential| sequential.set(self.0));
+    }
+}
+
+impl Execution {
+    /// Runs `f` with this execution mode in effect
+    pub fn install<R: Send>(self, f: impl FnOnce() -> R + Send) -> R {
+        match self {
+            Execution::Parallel => f(),
+            Execution::Threads(threads) => ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .expect("failed to build rayon thread pool")
+                .install(f),
+            Execution::Sequential => {
+                let _guard =
+                    SequentialGuard(SEQUENTIAL.with(|sequential| sequential.replace(true)));
+                f()
+            }
+        }
+    }
+
+    /// Number of threads solvers get in this mode
+    pub fn threads(self) -> usize {
+        match self {
+            Execution::Parallel => rayon::current_num_threads(),
+            Execution::Threads(threads) => threads,
+            Execution::Sequential => 1,
+        }
+    }
+}
+
+pub fn is_sequential() -> bool {
+    SEQUENTIAL.with(Cell::get)
+}
+
+/// Maps every item and folds the results with `reduce`, which has to be
+/// associative and commutative for both paths to agree
+pub fn map_reduce<C, T, R, M, I, F>(items: C, map: M, identity: I, reduce: F) -> R
+where
+    C: IntoParallelIterator<Item = T> + IntoIterator<Item = T>,
+    T: Send,
+    R: Send,
+    M: Fn(T) -> R + Sync + Send,
+    I: Fn() -> R + Sync + Send,
+    F: Fn(R, R) -> R + Sync + Send,
+{
+    if is_sequential() {
+        IntoIterator::into_iter(items)
+            .map(map)
+            .fold(identity(), reduce)
+    } else {
+        items.into_par_iter().map(map).reduce(identity, reduce)
+    }
+}
+
+pub fn map_sum<C, T, S, M>(items: C, map: M) -> S
+where
+    C: IntoParallelIterator<Item = T> + IntoIterator<Item = T>,
+    T: Send,
+    S: Sum + Send,
+    M: Fn(T) -> S + Sync + Send,
+{
+    if is_sequential() {
+        IntoIterator::into_iter(items).map(map).sum()
+    } else {
+        items.into_par_iter().map(map).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    const MODES: [Execution; 3] = [
+        Execution::Parallel,
+        Execution::Threads(2),
+        Execution::Sequential,
+    ];
+
+    #[test]
+    fn test_modes_agree() {
+        for execution in MODES {
+            let (sum, min) = execution.install(|| {
+                (
+                    map_sum(0u64..10_000, |n| n * n),
+                    map_reduce(1u64..10_000, |n| n % 97 + n / 97, || u64::MAX, u64::min),
+                )
+            });
+            assert_eq!(sum, 333_283_335_000);
+            assert_eq!(min, 1);
+        }
+    }
+
+    #[test]
+    fn test_sequential_stays_on_calling_thread() {
+        let caller = thread::current().id();
+        let all_on_caller = Execution::Sequential.install(|| {
+            map_reduce(
+                0..1000,
+                |_| thread::current().id() == caller,
+                || true,
+                |a, b| a && b,
+            )
+        });
+        assert!(all_on_caller);
+        assert!(!is_sequential());
+    }
+
+    #[test]
+    fn test_threads() {
+        assert_eq!(Execution::Threads(3).install(rayon::current_num_threads), 3);
+        assert_eq!(Execution::Sequential.threads(), 1);
+    }
+}
@@ -0,0 +1,63 @@
+//! Areas of lattice polygons.
+
+use crate::helpers::gcd;
+
+/// Twice the signed area of the polygon through `vertices` (shoelace
+/// formula), positive when they go counter-clockwise in a y-up frame. The
+/// polygon closes itself, the last vertex must not repeat the first.
+pub fn shoelace_area2(vertices: &[(i64, i64)]) -> i64 {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&(x1, y1), &(x2, y2))| x1 * y2 - x2 * y1)
+        .sum()
+}
+
+/// Lattice points on the boundary of the polygon through `vertices`
+pub fn boundary_points(vertices: &[(i64, i64)]) -> i64 {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&(x1, y1), &(x2, y2))| {
+            gcd(
+                (x2 - x1).unsigned_abs() as usize,
+                (y2 - y1).unsigned_abs() as usize,
+            ) as i64
+        })
+        .sum()
+}
+
+/// Lattice points strictly inside a polygon, by Pick's theorem
+/// (`A = i + b/2 - 1`), from twice its area and its boundary points
+pub fn interior_points(area2: i64, boundary: i64) -> i64 {
+    (area2.abs() - boundary) / 2 + 1
+}
+
+/// Lattice points inside or on the polygon through `vertices`, i.e. the
+/// unit cells covered when every point is the centre of one
+pub fn covered_points(vertices: &[(i64, i64)]) -> i64 {
+    let boundary = boundary_points(vertices);
+
+    interior_points(shoelace_area2(vertices), boundary) + boundary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        assert_eq!(shoelace_area2(&square), 32);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(shoelace_area2(&square), 16), 9);
+        assert_eq!(covered_points(&square), 25);
+    }
+
+    #[test]
+    fn test_clockwise_is_negative() {
+        let triangle = [(0, 0), (0, 3), (3, 0)];
+        assert_eq!(shoelace_area2(&triangle), -9);
+        assert_eq!(interior_points(-9, boundary_points(&triangle)), 1);
+    }
+}
@@ -0,0 +1,289 @@
+//! Dense row-major grid for the map-shaped puzzles, with positions that
+//! stay inside it and the four directions to move between them.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::parsing::{only, ParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Pos {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Pos { x, y }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from `Up`
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    /// `(dx, dy)` with `y` growing downwards
+    pub fn delta(self) -> (i64, i64) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+
+    /// Index into [`Direction::ALL`], handy for bitmasks
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl Grid<u8> {
+    /// One cell per byte, one row per line. Every row must be as wide as
+    /// the first.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input.lines();
+        let first = lines.next().ok_or(ParseError::Missing("grid rows"))?;
+        let width = first.len();
+        let mut cells = first.as_bytes().to_vec();
+
+        for (row, line) in lines.enumerate() {
+            if line.len() != width {
+                return Err(ParseError::RaggedRow(row + 1));
+            }
+            cells.extend_from_slice(line.as_bytes());
+        }
+
+        Ok(Grid {
+            width,
+            height: cells.len() / width.max(1),
+            cells,
+        })
+    }
+
+    /// [`Grid::parse`], with every cell one of the characters of `alphabet`
+    pub fn parse_only(input: &str, alphabet: &str) -> Result<Self, ParseError> {
+        for line in input.lines() {
+            only(line, alphabet)?;
+        }
+        Grid::parse(input)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Pos::new(x, y)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        (0..self.width as i64).contains(&x) && (0..self.height as i64).contains(&y)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        (pos.x < self.width && pos.y < self.height).then(|| &self.cells[self.offset(pos)])
+    }
+
+    fn offset(&self, pos: Pos) -> usize {
+        pos.y * self.width + pos.x
+    }
+
+    /// The neighbour of `pos` towards `direction`, if it is inside
+    pub fn step(&self, pos: Pos, direction: Direction) -> Option<Pos> {
+        let (dx, dy) = direction.delta();
+        let (x, y) = (pos.x as i64 + dx, pos.y as i64 + dy);
+        self.contains(x, y)
+            .then(|| Pos::new(x as usize, y as usize))
+    }
+
+    /// Neighbours of `pos` inside the grid, with the direction to each
+    pub fn neighbours(&self, pos: Pos) -> impl Iterator<Item = (Direction, Pos)> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| Some((direction, self.step(pos, direction)?)))
+    }
+
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Pos::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find_map(|(pos, cell)| predicate(cell).then_some(pos))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Pos, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(pos, cell)| f(pos, cell)).collect(),
+        }
+    }
+
+    /// Draws the grid a character per cell, a line per row
+    pub fn render(&self, mut f: impl FnMut(Pos, &T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for (pos, cell) in self.iter() {
+            out.push(f(pos, cell));
+            if pos.x + 1 == self.width {
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(
+            pos.x < self.width && pos.y < self.height,
+            "{pos:?} is outside"
+        );
+        &self.cells[self.offset(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(
+            pos.x < self.width && pos.y < self.height,
+            "{pos:?} is outside"
+        );
+        let offset = self.offset(pos);
+        &mut self.cells[offset]
+    }
+}
+
+impl fmt::Display for Grid<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(|_, &cell| cell as char))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = "ab.\n.#c\n";
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = Grid::parse(SAMPLE).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Pos::new(1, 1)], b'#');
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert_eq!(grid.position(|&c| c == b'c'), Some(Pos::new(2, 1)));
+        assert_eq!(grid.to_string(), SAMPLE);
+        assert_eq!(Grid::parse("ab\nc").unwrap_err(), ParseError::RaggedRow(1));
+    }
+
+    #[test]
+    fn test_parse_only() {
+        let cases = [
+            ("#.\n.#", Ok((2, 2))),
+            ("#.\r\n.#\r\n", Ok((2, 2))),
+            ("#.\n.x", Err(ParseError::InvalidChar('x'))),
+            ("#.\n.", Err(ParseError::RaggedRow(1))),
+            ("", Err(ParseError::Missing("grid rows"))),
+        ];
+        for (input, expected) in cases {
+            let grid = Grid::parse_only(input, ".#");
+            assert_eq!(
+                grid.map(|grid| (grid.width(), grid.height())),
+                expected,
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_steps_stay_inside() {
+        let grid = Grid::new(2, 2, 0u8);
+        let corner = Pos::new(0, 0);
+        assert_eq!(grid.step(corner, Direction::Up), None);
+        assert_eq!(grid.step(corner, Direction::Right), Some(Pos::new(1, 0)));
+        assert_eq!(
+            grid.neighbours(corner).collect::<Vec<_>>(),
+            vec![
+                (Direction::Right, Pos::new(1, 0)),
+                (Direction::Down, Pos::new(0, 1))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+    }
+}
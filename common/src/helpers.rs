@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub fn lcm(a: usize, b: usize) -> usize {
+    (a * b) / gcd(a, b)
+}
+
+/// The value following `values`, read off their table of differences.
+/// The table is built in place, so `values` is left scrambled.
+pub fn extrapolate(values: &mut [i64]) -> i64 {
+    (0..values.len())
+        .rev()
+        .map(|len| {
+            for i in 0..len {
+                values[i] = values[i + 1] - values[i];
+            }
+            values[len]
+        })
+        .sum()
+}
+
+/// `(g, x, y)` with `g = gcd(a, b) = a * x + b * y`
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
+
+/// Combines the congruences `x ≡ remainder (mod modulus)` into one, by the
+/// Chinese remainder theorem. The moduli don't need to be coprime; `None`
+/// when no `x` satisfies them all.
+pub fn crt(congruences: impl IntoIterator<Item = (u64, u64)>) -> Option<(u64, u64)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+            let (r1, m1, m2) = (r1 as i128, m1 as i128, m2 as i128);
+            let r2 = r2 as i128 % m2;
+            let (g, p, _) = extended_gcd(m1, m2);
+            if (r2 - r1) % g != 0 {
+                return None;
+            }
+
+            // m1 * k ≡ r2 - r1 (mod m2), solved for k
+            let step = m2 / g;
+            let k = ((r2 - r1) / g % step * (p % step)).rem_euclid(step);
+            let modulus = m1 * step;
+            Some(((r1 + m1 * k).rem_euclid(modulus) as u64, modulus as u64))
+        })
+}
+
+/// A sequence of states that repeats from `start` on, every `length` steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step showing the same state as step `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Applies `step` from `initial` until a state comes back. Returns the cycle
+/// and every state up to the repeat, `states[i]` being the one after `i`
+/// steps.
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> (Cycle, Vec<S>)
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![initial];
+
+    loop {
+        let current = states.last().unwrap();
+        if let Some(&start) = seen.get(current) {
+            states.pop();
+            let length = states.len() - start;
+            return (Cycle { start, length }, states);
+        }
+        seen.insert(current.clone(), states.len() - 1);
+        let next = step(current);
+        states.push(next);
+    }
+}
+
+/// The state after `n` steps, looked up in the cycle [`find_cycle`] finds.
+/// The sequence is always followed up to its first repeat, even for an `n`
+/// before that.
+pub fn nth_state<S, F>(initial: S, n: usize, step: F) -> S
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let (cycle, mut states) = find_cycle(initial, step);
+    states.swap_remove(cycle.reduce(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, 3, 4, 2, 3, 4, ...
+    fn step(&n: &u32) -> u32 {
+        if n == 4 {
+            2
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let (cycle, states) = find_cycle(0, step);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 2,
+                length: 3
+            }
+        );
+        assert_eq!(states, vec![0, 1, 2, 3, 4]);
+        assert_eq!(cycle.reduce(1), 1);
+        assert_eq!(cycle.reduce(8), 2);
+    }
+
+    #[test]
+    fn test_nth_state() {
+        let mut state = 0;
+        for n in 0..20 {
+            assert_eq!(nth_state(0, n, step), state);
+            state = step(&state);
+        }
+        assert_eq!(nth_state(0, 1_000_000_000, step), 4);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4, 6), 12);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        assert_eq!(extrapolate(&mut [0, 3, 6, 9, 12, 15]), 18);
+        assert_eq!(extrapolate(&mut [3, 14, 39, 78]), 131);
+        assert_eq!(extrapolate(&mut [7]), 7);
+        assert_eq!(extrapolate(&mut []), 0);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(0, 4), (2, 6)]), Some((8, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(0, 3739), (0, 3761)]), Some((0, 3739 * 3761)));
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+}
@@ -0,0 +1,152 @@
+//! Minimal HTTP abstraction used to talk to the puzzle server, so the
+//! transport can be swapped out (and pointed at a local server in tests).
+
+use std::time::Duration;
+
+/// The puzzle server asks automated tools to identify themselves
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), " by ", env!("CARGO_PKG_AUTHORS"));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+pub trait HttpClient {
+    /// GET `url` with the given cookie header, non-2xx statuses are returned
+    /// as responses, only transport failures are errors
+    fn get(&self, url: &str, cookie: &str) -> Result<HttpResponse, String>;
+
+    /// POST `form` url-encoded to `url`, statuses are handled like in `get`
+    fn post_form(
+        &self,
+        url: &str,
+        cookie: &str,
+        form: &[(&str, &str)],
+    ) -> Result<HttpResponse, String>;
+}
+
+/// Default backend on top of `ureq`
+pub struct UreqClient {
+    agent: ureq::Agent,
+}
+
+impl Default for UreqClient {
+    fn default() -> Self {
+        UreqClient {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+}
+
+fn into_response(result: Result<ureq::Response, ureq::Error>) -> Result<HttpResponse, String> {
+    let response = match result {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => return Err(e.to_string()),
+    };
+    let status = response.status();
+    let body = response.into_string().map_err(|e| e.to_string())?;
+    Ok(HttpResponse { status, body })
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, cookie: &str) -> Result<HttpResponse, String> {
+        into_response(self.agent.get(url).set("Cookie", cookie).call())
+    }
+
+    fn post_form(
+        &self,
+        url: &str,
+        cookie: &str,
+        form: &[(&str, &str)],
+    ) -> Result<HttpResponse, String> {
+        into_response(self.agent.post(url).set("Cookie", cookie).send_form(form))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_server {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+
+    /// A request as the stand-in server saw it
+    #[derive(Debug)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<String>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers.iter().find_map(|header| {
+                let (key, value) = header.split_once(':')?;
+                key.eq_ignore_ascii_case(name).then(|| value.trim())
+            })
+        }
+    }
+
+    /// Serves the canned `(status, body)` responses in order, one per
+    /// connection, on a local port. Returns the base URL and the requests
+    /// received.
+    pub fn serve(responses: Vec<(u16, String)>) -> (String, Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut parts = request_line.split_whitespace();
+                let method = parts.next().unwrap_or_default().to_string();
+                let path = parts.next().unwrap_or_default().to_string();
+
+                let mut headers = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    headers.push(line.to_string());
+                }
+
+                let mut request = Request {
+                    method,
+                    path,
+                    headers,
+                    body: String::new(),
+                };
+                let length = request
+                    .header("Content-Length")
+                    .map_or(0, |length| length.parse().unwrap());
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+                request.body = String::from_utf8(request_body).unwrap();
+                sender.send(request).unwrap();
+
+                let mut stream = stream;
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Canned\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, receiver)
+    }
+}
@@ -0,0 +1,253 @@
+//! Puzzle inputs cached on disk as `dayN.txt`, each next to a
+//! `dayN.txt.sha256` checksum written when the input was stored. Missing
+//! inputs can be downloaded from the puzzle server when a session is
+//! configured.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::http::{HttpClient, UreqClient};
+use crate::runner::sha256_hex;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug)]
+pub enum InputError {
+    Io(PathBuf, io::Error),
+    /// The request never got a response
+    Transport(String),
+    /// The server answered with something other than 200
+    Http(u16, String),
+    /// The cached input does not match its stored checksum
+    ChecksumMismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+    /// Not on disk and no session to download it with
+    NotCached(PathBuf),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            InputError::Transport(e) => write!(f, "request failed: {e}"),
+            InputError::Http(status, body) => {
+                write!(f, "server answered {status}: {}", body.trim())
+            }
+            InputError::ChecksumMismatch {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} has checksum {actual}, expected {expected}",
+                path.display()
+            ),
+            InputError::NotCached(path) => write!(
+                f,
+                "{} is missing, pass --fetch with a session to download it",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// Where and how to download missing inputs
+pub struct Remote {
+    pub base_url: String,
+    pub session: String,
+    pub client: Box<dyn HttpClient>,
+}
+
+impl Remote {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Remote {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            client: Box::new(UreqClient::default()),
+        }
+    }
+
+    pub fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+pub struct InputStore {
+    pub year: u32,
+    pub dir: PathBuf,
+    pub remote: Option<Remote>,
+}
+
+impl InputStore {
+    /// A cache-only store, nothing gets downloaded
+    pub fn new(year: u32, dir: &Path) -> Self {
+        InputStore {
+            year,
+            dir: dir.to_path_buf(),
+            remote: None,
+        }
+    }
+
+    pub fn with_remote(mut self, remote: Remote) -> Self {
+        self.remote = Some(remote);
+        self
+    }
+
+    pub fn path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{day}.txt"))
+    }
+
+    fn checksum_path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{day}.txt.sha256"))
+    }
+
+    /// Returns the input for `day`, downloading and storing it first when
+    /// it is missing and a remote is configured
+    pub fn resolve(&self, day: u32) -> Result<String, InputError> {
+        let path = self.path(day);
+        match fs::read(&path) {
+            Ok(bytes) => {
+                self.verify(day, &bytes)?;
+                String::from_utf8(bytes).map_err(|e| {
+                    InputError::Io(path, io::Error::new(io::ErrorKind::InvalidData, e))
+                })
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => match &self.remote {
+                Some(remote) => {
+                    let input = self.download(remote, day)?;
+                    self.store(day, &input)?;
+                    Ok(input)
+                }
+                None => Err(InputError::NotCached(path)),
+            },
+            Err(e) => Err(InputError::Io(path, e)),
+        }
+    }
+
+    /// Checks `bytes` against the stored checksum, inputs copied in by hand
+    /// have none and are accepted as they are
+    fn verify(&self, day: u32, bytes: &[u8]) -> Result<(), InputError> {
+        let checksum_path = self.checksum_path(day);
+        let expected = match fs::read_to_string(&checksum_path) {
+            Ok(expected) => expected.trim().to_string(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(InputError::Io(checksum_path, e)),
+        };
+
+        let actual = sha256_hex(bytes);
+        if actual != expected {
+            return Err(InputError::ChecksumMismatch {
+                path: self.path(day),
+                expected,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    fn download(&self, remote: &Remote, day: u32) -> Result<String, InputError> {
+        let url = format!("{}/{}/day/{day}/input", remote.base_url, self.year);
+        let response = remote
+            .client
+            .get(&url, &remote.cookie())
+            .map_err(InputError::Transport)?;
+        if response.status != 200 {
+            return Err(InputError::Http(response.status, response.body));
+        }
+        Ok(response.body)
+    }
+
+    /// Writes the input and its checksum
+    pub fn store(&self, day: u32, input: &str) -> Result<(), InputError> {
+        fs::create_dir_all(&self.dir).map_err(|e| InputError::Io(self.dir.clone(), e))?;
+        let path = self.path(day);
+        fs::write(&path, input).map_err(|e| InputError::Io(path, e))?;
+        let checksum_path = self.checksum_path(day);
+        fs::write(&checksum_path, sha256_hex(input.as_bytes()) + "\n")
+            .map_err(|e| InputError::Io(checksum_path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::test_server;
+    use std::env;
+
+    static INPUT: &str = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n";
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-inputs-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_downloads_and_stores_missing_input() {
+        let (base_url, requests) = test_server::serve(vec![(200, INPUT.to_string())]);
+        let dir = cache_dir("download");
+        let store = InputStore::new(2023, &dir).with_remote(Remote::new(&base_url, "abc"));
+
+        assert_eq!(store.resolve(9).unwrap(), INPUT);
+        let request = requests.recv().unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/2023/day/9/input");
+        assert_eq!(request.header("Cookie"), Some("session=abc"));
+
+        assert_eq!(fs::read_to_string(dir.join("day9.txt")).unwrap(), INPUT);
+        assert_eq!(
+            fs::read_to_string(dir.join("day9.txt.sha256"))
+                .unwrap()
+                .trim(),
+            sha256_hex(INPUT.as_bytes())
+        );
+
+        // Served from the cache now, the server only had one response
+        assert_eq!(store.resolve(9).unwrap(), INPUT);
+    }
+
+    #[test]
+    fn test_http_error_stores_nothing() {
+        let (base_url, _requests) =
+            test_server::serve(vec![(400, "Puzzle inputs differ by user.".to_string())]);
+        let dir = cache_dir("http-error");
+        let store = InputStore::new(2023, &dir).with_remote(Remote::new(&base_url, "expired"));
+
+        assert!(matches!(store.resolve(1), Err(InputError::Http(400, _))));
+        assert!(!dir.join("day1.txt").exists());
+    }
+
+    #[test]
+    fn test_cache_only() {
+        let dir = cache_dir("cache-only");
+        let store = InputStore::new(2023, &dir);
+        assert!(matches!(store.resolve(2), Err(InputError::NotCached(_))));
+
+        // Copied by hand, without a checksum
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day2.txt"), INPUT).unwrap();
+        assert_eq!(store.resolve(2).unwrap(), INPUT);
+    }
+
+    #[test]
+    fn test_checksum_mismatch() {
+        let dir = cache_dir("checksum");
+        let store = InputStore::new(2023, &dir);
+        store.store(3, INPUT).unwrap();
+        assert_eq!(store.resolve(3).unwrap(), INPUT);
+
+        fs::write(dir.join("day3.txt"), "edited").unwrap();
+        assert!(matches!(
+            store.resolve(3),
+            Err(InputError::ChecksumMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,44 @@
+//! Half-open `u64` ranges, for the puzzles that push whole ranges of
+//! values through rules instead of one value at a time.
+
+use std::ops::Range;
+
+/// The values in both ranges
+pub fn intersect(a: &Range<u64>, b: &Range<u64>) -> Option<Range<u64>> {
+    let range = a.start.max(b.start)..a.end.min(b.end);
+    (!range.is_empty()).then_some(range)
+}
+
+/// The values below `at` and the ones from `at` on, either side `None`
+/// when empty
+pub fn split(range: &Range<u64>, at: u64) -> (Option<Range<u64>>, Option<Range<u64>>) {
+    (
+        intersect(range, &(0..at)),
+        intersect(range, &(at..u64::MAX)),
+    )
+}
+
+pub fn len(range: &Range<u64>) -> u64 {
+    range.end.saturating_sub(range.start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersect() {
+        assert_eq!(intersect(&(1..10), &(5..20)), Some(5..10));
+        assert_eq!(intersect(&(1..5), &(5..20)), None);
+        assert_eq!(intersect(&(3..4), &(0..10)), Some(3..4));
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(split(&(1..10), 4), (Some(1..4), Some(4..10)));
+        assert_eq!(split(&(1..10), 1), (None, Some(1..10)));
+        assert_eq!(split(&(1..10), 10), (Some(1..10), None));
+        assert_eq!(len(&(1..10)), 9);
+        assert_eq!(len(&(5..5)), 0);
+    }
+}
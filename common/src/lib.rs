@@ -0,0 +1,16 @@
+//! Code shared by every year: the solving helpers the days build on, and the
+//! runner with its input cache and answer submission.
+
+pub mod execution;
+pub mod geometry;
+pub mod grid;
+pub mod helpers;
+pub mod http;
+pub mod inputs;
+pub mod intervals;
+pub mod parsing;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod streaming;
+pub mod submit;
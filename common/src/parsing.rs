@@ -0,0 +1,72 @@
+use std::{error::Error, fmt, str::FromStr};
+
+/// Why a piece of puzzle input could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A field or delimiter the format requires is not there
+    Missing(&'static str),
+    /// Text that should be a number but is not
+    InvalidNumber(String),
+    /// A character outside of the puzzle's alphabet
+    InvalidChar(char),
+    /// A node referenced but never defined
+    UnknownNode(String),
+    /// A node that leads back to itself
+    Cycle(String),
+    /// A value that does not fit the integer type it is stored in
+    Overflow(&'static str),
+    /// Input that parses but that the puzzle rules out
+    Invalid(&'static str),
+    /// A grid row (0-based) of a different width than the first one
+    RaggedRow(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Missing(what) => write!(f, "missing {what}"),
+            ParseError::InvalidNumber(s) => write!(f, "invalid number '{s}'"),
+            ParseError::InvalidChar(ch) => write!(f, "invalid character '{ch}'"),
+            ParseError::UnknownNode(name) => write!(f, "unknown node '{name}'"),
+            ParseError::Cycle(name) => write!(f, "node '{name}' leads back to itself"),
+            ParseError::Overflow(what) => write!(f, "{what} overflows"),
+            ParseError::Invalid(what) => write!(f, "invalid {what}"),
+            ParseError::RaggedRow(row) => write!(f, "row {row} has a different width"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn parse_number<T: FromStr>(s: &str) -> Result<T, ParseError> {
+    s.parse()
+        .map_err(|_| ParseError::InvalidNumber(s.to_string()))
+}
+
+/// `s` as it is, or the first character of it not in `alphabet`
+pub fn only<'a>(s: &'a str, alphabet: &str) -> Result<&'a str, ParseError> {
+    match s.chars().find(|&ch| !alphabet.contains(ch)) {
+        Some(invalid) => Err(ParseError::InvalidChar(invalid)),
+        None => Ok(s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only() {
+        let cases = [
+            ("..#", ".#", Ok("..#")),
+            ("", ".#", Ok("")),
+            ("#?x", ".#?", Err(ParseError::InvalidChar('x'))),
+            ("O#.\n", "O#.", Err(ParseError::InvalidChar('\n'))),
+            ("12a", "0123456789", Err(ParseError::InvalidChar('a'))),
+            ("./\\|-é", "./\\|-", Err(ParseError::InvalidChar('é'))),
+        ];
+        for (s, alphabet, expected) in cases {
+            assert_eq!(only(s, alphabet), expected, "{s:?} in {alphabet:?}");
+        }
+    }
+}
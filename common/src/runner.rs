@@ -0,0 +1,366 @@
+//! Runs the registered solutions against the inputs on disk and records
+//! answers, timings and input metadata in a report that can be printed as a
+//! table or serialized to JSON.
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::execution::Execution;
+use crate::inputs::InputStore;
+
+/// A day's parse step, its error already rendered
+pub type Parse = fn(&str) -> Result<(), String>;
+
+/// One `#[aoc]` solution, mirrored here so the runner can call it directly
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<&'static str>,
+    /// The day's `parse` step on its own, for days that expose one
+    pub parse: Option<Parse>,
+    pub solve: fn(&str) -> String,
+}
+
+#[macro_export]
+macro_rules! solution {
+    ($day:literal, $part:literal, $variant:expr, $solve:path) => {
+        $crate::runner::Solution {
+            day: $day,
+            part: $part,
+            variant: $variant,
+            parse: None,
+            solve: |input| $solve(input).to_string(),
+        }
+    };
+    ($day:literal, $part:literal, $variant:expr, $solve:path, $parse:path) => {
+        $crate::runner::Solution {
+            day: $day,
+            part: $part,
+            variant: $variant,
+            parse: Some(|input| $parse(input).map(drop).map_err(|e| e.to_string())),
+            solve: |input| $solve(input).to_string(),
+        }
+    };
+}
+
+/// The solutions of one year, generated by each year crate's `build.rs`
+pub struct Year {
+    pub year: u32,
+    pub solutions: &'static [Solution],
+}
+
+/// Which solutions to run, `None` matches everything
+#[derive(Debug, Default, Clone)]
+pub struct Filter {
+    pub day: Option<u32>,
+    pub part: Option<u32>,
+    pub variant: Option<String>,
+}
+
+impl Filter {
+    pub fn matches(&self, solution: &Solution) -> bool {
+        self.day.is_none_or(|day| day == solution.day)
+            && self.part.is_none_or(|part| part == solution.part)
+            && self
+                .variant
+                .as_deref()
+                .is_none_or(|variant| Some(variant) == solution.variant)
+    }
+
+    /// Days with at least one matching solution, in order
+    pub fn days(&self, year: &Year) -> Vec<u32> {
+        let mut days: Vec<u32> = year
+            .solutions
+            .iter()
+            .filter(|solution| self.matches(solution))
+            .map(|solution| solution.day)
+            .collect();
+        days.dedup();
+        days
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub year: u32,
+    pub execution: Execution,
+    /// Threads the solutions ran on, 1 when sequential
+    pub threads: usize,
+    pub runs: Vec<Run>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Run {
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<&'static str>,
+    pub answer: Option<String>,
+    /// Time spent reading the input file and decoding it
+    pub read_ns: u64,
+    /// Time the day's `parse` step takes on its own, missing for days
+    /// without one
+    pub parse_ns: Option<u64>,
+    /// Time the whole solution takes, its own parsing included
+    pub solve_ns: u64,
+    pub input_path: PathBuf,
+    /// SHA-256 of the input file, missing when it could not be read
+    pub input_sha256: Option<String>,
+    pub error: Option<String>,
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "solution panicked".to_string()
+    }
+}
+
+fn run_solution(solution: &Solution, inputs: &InputStore) -> Run {
+    let mut run = Run {
+        day: solution.day,
+        part: solution.part,
+        variant: solution.variant,
+        answer: None,
+        read_ns: 0,
+        parse_ns: None,
+        solve_ns: 0,
+        input_path: inputs.path(solution.day),
+        input_sha256: None,
+        error: None,
+    };
+
+    // Checked against the stored checksum, a corrupted cache is an error
+    let read_start = Instant::now();
+    let input = match inputs.resolve(solution.day) {
+        Ok(input) => input,
+        Err(e) => {
+            run.error = Some(e.to_string());
+            return run;
+        }
+    };
+    run.input_sha256 = Some(sha256_hex(input.as_bytes()));
+    // Same trimming aoc-runner applies to its inputs
+    let input = input.trim_end_matches('\n');
+    run.read_ns = read_start.elapsed().as_nanos() as u64;
+
+    if let Some(parse) = solution.parse {
+        let parse_start = Instant::now();
+        let parsed = parse(input);
+        run.parse_ns = Some(parse_start.elapsed().as_nanos() as u64);
+        if let Err(e) = parsed {
+            run.error = Some(e);
+            return run;
+        }
+    }
+
+    let solve_start = Instant::now();
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| (solution.solve)(input)));
+    run.solve_ns = solve_start.elapsed().as_nanos() as u64;
+
+    match answer {
+        Ok(answer) => run.answer = Some(answer),
+        Err(payload) => run.error = Some(panic_message(payload)),
+    }
+
+    run
+}
+
+pub fn run(year: &Year, filter: &Filter, input_dir: &Path, execution: Execution) -> Report {
+    Report {
+        year: year.year,
+        execution,
+        threads: execution.threads(),
+        runs: execution.install(|| {
+            let inputs = InputStore::new(year.year, input_dir);
+            year.solutions
+                .iter()
+                .filter(|solution| filter.matches(solution))
+                .map(|solution| run_solution(solution, &inputs))
+                .collect()
+        }),
+    }
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("report is always serializable")
+    }
+
+    /// The answer every successful variant of `day` and `part` agreed on,
+    /// which is what gets submitted
+    pub fn answer(&self, day: u32, part: u32) -> Result<&str, String> {
+        let mut answers = self
+            .runs
+            .iter()
+            .filter(|run| (run.day, run.part) == (day, part))
+            .filter_map(|run| run.answer.as_deref());
+        let answer = answers
+            .next()
+            .ok_or_else(|| format!("no answer for day {day} part {part}"))?;
+        match answers.find(|other| *other != answer) {
+            Some(other) => Err(format!(
+                "variants of day {day} part {part} disagree: {answer} and {other}"
+            )),
+            None => Ok(answer),
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = match self.execution {
+            Execution::Sequential => "sequential".to_string(),
+            _ => format!("{} threads", self.threads),
+        };
+        writeln!(f, "Advent of code {} ({mode})", self.year)?;
+        writeln!(
+            f,
+            "{:>3} {:>4}  {:<34} {:>16} {:>12} {:>12} {:>12}",
+            "Day", "Part", "Variant", "Answer", "Read", "Parse", "Solve"
+        )?;
+        for run in &self.runs {
+            write!(
+                f,
+                "{:>3} {:>4}  {:<34} {:>16} {:>12} {:>12} {:>12}",
+                run.day,
+                run.part,
+                run.variant.unwrap_or("-"),
+                run.answer.as_deref().unwrap_or("FAILED"),
+                format!("{:?}", Duration::from_nanos(run.read_ns)),
+                run.parse_ns.map_or("-".to_string(), |parse_ns| format!(
+                    "{:?}",
+                    Duration::from_nanos(parse_ns)
+                )),
+                format!("{:?}", Duration::from_nanos(run.solve_ns)),
+            )?;
+            match &run.error {
+                Some(error) => writeln!(f, "  {error}")?,
+                None => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::{parse_number, ParseError};
+    use std::env;
+    use std::fs;
+
+    fn count_lines(input: &str) -> usize {
+        input.lines().count()
+    }
+
+    fn first_line_length(input: &str) -> usize {
+        input.lines().next().expect("empty input").len()
+    }
+
+    fn parse_numbers(input: &str) -> Result<Vec<u64>, ParseError> {
+        input.lines().map(parse_number).collect()
+    }
+
+    fn sum_numbers(input: &str) -> u64 {
+        parse_numbers(input).unwrap().iter().sum()
+    }
+
+    static YEAR: Year = Year {
+        year: 2000,
+        solutions: &[
+            solution!(1, 1, Some("Lines"), count_lines),
+            solution!(1, 2, Some("Length"), first_line_length),
+            solution!(1, 2, Some("Lines"), count_lines),
+            solution!(2, 1, None, sum_numbers, parse_numbers),
+        ],
+    };
+
+    #[test]
+    fn test_run_catches_panics_and_disagreements() {
+        let dir = env::temp_dir().join(format!("aoc-runner-common-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day1.txt"), "abc\nde\n").unwrap();
+
+        let report = run(&YEAR, &Filter::default(), &dir, Execution::Sequential);
+        assert_eq!(report.year, 2000);
+        assert_eq!(report.answer(1, 1), Ok("2"));
+        assert!(report.answer(1, 2).unwrap_err().contains("disagree"));
+
+        fs::write(dir.join("day1.txt"), "").unwrap();
+        let filter = Filter {
+            variant: Some("Length".to_string()),
+            ..Filter::default()
+        };
+        let report = run(&YEAR, &filter, &dir, Execution::Sequential);
+        assert_eq!(report.runs[0].error.as_deref(), Some("empty input"));
+        assert_eq!(filter.days(&YEAR), vec![1]);
+    }
+
+    #[test]
+    fn test_run_times_the_parse_step() {
+        let dir = env::temp_dir().join(format!("aoc-runner-parse-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day1.txt"), "abc\nde\n").unwrap();
+        fs::write(dir.join("day2.txt"), "1\n2\n3\n").unwrap();
+
+        let report = run(&YEAR, &Filter::default(), &dir, Execution::Sequential);
+        assert_eq!(report.runs[0].parse_ns, None);
+        assert!(report.runs[3].parse_ns.is_some());
+        assert_eq!(report.answer(2, 1), Ok("6"));
+
+        // A parse error stops the run before the solver panics on it
+        fs::write(dir.join("day2.txt"), "1\nx\n").unwrap();
+        let filter = Filter {
+            day: Some(2),
+            ..Filter::default()
+        };
+        let report = run(&YEAR, &filter, &dir, Execution::Sequential);
+        assert_eq!(report.runs[0].answer, None);
+        assert_eq!(
+            report.runs[0].error.as_deref(),
+            Some(
+                ParseError::InvalidNumber("x".to_string())
+                    .to_string()
+                    .as_str()
+            )
+        );
+    }
+
+    #[test]
+    fn test_run_rejects_corrupted_input() {
+        let dir = env::temp_dir().join(format!("aoc-runner-checksum-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let inputs = InputStore::new(YEAR.year, &dir);
+        inputs.store(1, "abc\nde\n").unwrap();
+        fs::write(dir.join("day1.txt"), "abc\nXX\n").unwrap();
+
+        let filter = Filter {
+            day: Some(1),
+            ..Filter::default()
+        };
+        let report = run(&YEAR, &filter, &dir, Execution::Sequential);
+        for run in &report.runs {
+            assert_eq!(run.answer, None);
+            assert!(run.error.as_deref().unwrap().contains("expected"));
+        }
+
+        fs::remove_file(dir.join("day1.txt")).unwrap();
+        let report = run(&YEAR, &filter, &dir, Execution::Sequential);
+        assert!(report.runs[0].error.as_deref().unwrap().contains("missing"));
+    }
+}
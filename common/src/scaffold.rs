@@ -0,0 +1,197 @@
+//! Creates the skeleton of a new day in a year crate: the `dayN.rs` module
+//! with both `#[aoc]` parts and a tests module holding an empty sample and
+//! its expected answers, the `mod dayN;` declaration in `lib.rs`, and the
+//! input directory the runner reads `dayN.txt` from. The solution registry
+//! picks the new module up on the next build.
+//!
+//! The parts return 0 and the sample tests are ignored until the sample is
+//! filled in, so a freshly scaffolded day builds and keeps the test suite
+//! green.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// What was created, for printing
+#[derive(Debug)]
+pub struct Scaffold {
+    pub module: PathBuf,
+    pub lib: PathBuf,
+    pub input: PathBuf,
+}
+
+pub fn module_source(day: u32) -> String {
+    format!(
+        r##"#[aoc(day{day}, part1)]
+pub fn part1(_input: &str) -> u64 {{
+    0
+}}
+
+#[aoc(day{day}, part2)]
+pub fn part2(_input: &str) -> u64 {{
+    0
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    static SAMPLE: &str = r#""#;
+
+    #[test]
+    #[ignore = "fill in SAMPLE and the expected answer"]
+    fn test_part1() {{
+        assert_eq!(part1(SAMPLE), 0);
+    }}
+
+    #[test]
+    #[ignore = "fill in SAMPLE and the expected answer"]
+    fn test_part2() {{
+        assert_eq!(part2(SAMPLE), 0);
+    }}
+}}
+"##
+    )
+}
+
+/// Day number of a `mod dayN;` line
+fn declared_day(line: &str) -> Option<u32> {
+    let line = line.strip_prefix("pub ").unwrap_or(line);
+    line.strip_prefix("mod day")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+/// Adds `mod dayN;` to a `lib.rs`, keeping the days in the order rustfmt
+/// sorts them in. Returns `None` when the day is already declared.
+pub fn register(lib: &str, day: u32) -> Option<String> {
+    let lines: Vec<&str> = lib.lines().collect();
+    if lines.iter().any(|line| declared_day(line) == Some(day)) {
+        return None;
+    }
+
+    let first = !lines.iter().any(|line| declared_day(line).is_some());
+    let position = match lines
+        .iter()
+        .rposition(|line| declared_day(line).is_some_and(|d| d.to_string() < day.to_string()))
+    {
+        Some(previous) => previous + 1,
+        None => lines
+            .iter()
+            .position(|line| declared_day(line).is_some())
+            .unwrap_or_else(|| {
+                // No days yet, after the crate attributes and `extern crate`s
+                lines
+                    .iter()
+                    .rposition(|line| line.starts_with("extern crate"))
+                    .map_or(0, |last| last + 2)
+                    .min(lines.len())
+            }),
+    };
+
+    let declaration = format!("mod day{day};");
+    let mut out: Vec<&str> = lines[..position].to_vec();
+    out.push(&declaration);
+    // The first day gets its own block, apart from what follows
+    if first && lines.get(position).is_some_and(|line| !line.is_empty()) {
+        out.push("");
+    }
+    out.extend(&lines[position..]);
+    Some(out.join("\n") + "\n")
+}
+
+/// Scaffolds `day` in the year crate at `crate_dir`, with inputs under
+/// `input_dir`. Fails without touching anything if the day already exists.
+pub fn scaffold(crate_dir: &Path, input_dir: &Path, day: u32) -> io::Result<Scaffold> {
+    let module = crate_dir.join("src").join(format!("day{day}.rs"));
+    if module.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", module.display()),
+        ));
+    }
+
+    let lib = crate_dir.join("src").join("lib.rs");
+    let registered = register(&fs::read_to_string(&lib)?, day).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("day{day} is already declared in {}", lib.display()),
+        )
+    })?;
+
+    fs::write(&module, module_source(day))?;
+    fs::write(&lib, registered)?;
+    fs::create_dir_all(input_dir)?;
+
+    Ok(Scaffold {
+        module,
+        lib,
+        input: input_dir.join(format!("day{day}.txt")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    static LIB: &str = r#"extern crate aoc_runner;
+
+#[macro_use]
+extern crate aoc_runner_derive;
+
+mod day1;
+mod day2;
+mod day9;
+pub mod generators;
+
+aoc_lib! { year = 2023 }
+"#;
+
+    #[test]
+    fn test_register_keeps_days_in_order() {
+        let lib = register(LIB, 10).unwrap();
+        assert!(lib.contains("mod day1;\nmod day10;\nmod day2;"));
+
+        let lib = register(&lib, 3).unwrap();
+        assert!(lib.contains("mod day2;\nmod day3;\nmod day9;"));
+
+        let lib = register(&lib, 12).unwrap();
+        assert!(lib.contains("mod day10;\nmod day12;\nmod day2;"));
+
+        assert_eq!(register(&lib, 10), None);
+    }
+
+    #[test]
+    fn test_register_first_day() {
+        let lib = "extern crate aoc_runner;\n\n#[macro_use]\nextern crate aoc_runner_derive;\n\naoc_lib! { year = 2024 }\n";
+        let lib = register(lib, 1).unwrap();
+        assert!(lib.contains("extern crate aoc_runner_derive;\n\nmod day1;\n\naoc_lib!"));
+        let lib = register(&lib, 2).unwrap();
+        assert!(lib.contains("\n\nmod day1;\nmod day2;\n\naoc_lib!"));
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let crate_dir = root.join("years/2023");
+        fs::create_dir_all(crate_dir.join("src")).unwrap();
+        fs::write(crate_dir.join("src/lib.rs"), LIB).unwrap();
+
+        let created = scaffold(&crate_dir, &root.join("input/2023"), 10).unwrap();
+        let source = fs::read_to_string(&created.module).unwrap();
+        assert!(source.starts_with("#[aoc(day10, part1)]\npub fn part1(_input: &str)"));
+        assert!(!source.contains("todo!"));
+        assert!(source.contains("static SAMPLE: &str"));
+        assert!(fs::read_to_string(&created.lib)
+            .unwrap()
+            .contains("mod day10;"));
+        assert_eq!(created.input, root.join("input/2023/day10.txt"));
+        assert!(root.join("input/2023").is_dir());
+
+        let again = scaffold(&crate_dir, &root.join("input/2023"), 10).unwrap_err();
+        assert_eq!(again.kind(), io::ErrorKind::AlreadyExists);
+    }
+}
@@ -0,0 +1,101 @@
+//! Shortest paths over implicit weighted graphs, where the puzzle only says
+//! how to get from one state to the next.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// The cheapest way found to a goal, `states` from a start to the goal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S> {
+    pub cost: u64,
+    pub states: Vec<S>,
+}
+
+/// Dijkstra's search from every state in `starts` until one satisfying
+/// `is_goal` comes off the queue. `neighbours` yields the states one move
+/// away with the (non-negative) cost of that move.
+pub fn dijkstra<S, N, I, G>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: N,
+    mut is_goal: G,
+) -> Option<Path<S>>
+where
+    S: Hash + Eq + Clone + Ord,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+    G: FnMut(&S) -> bool,
+{
+    // The best known cost of every state and the state it was reached from
+    let mut best: HashMap<S, (u64, Option<S>)> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        best.insert(start.clone(), (0, None));
+        queue.push(Reverse((0, start)));
+    }
+
+    while let Some(Reverse((cost, state))) = queue.pop() {
+        if best[&state].0 < cost {
+            continue;
+        }
+        if is_goal(&state) {
+            let mut states = vec![state];
+            while let Some(previous) = &best[states.last().unwrap()].1 {
+                states.push(previous.clone());
+            }
+            states.reverse();
+            return Some(Path { cost, states });
+        }
+
+        for (next, step) in neighbours(&state) {
+            let cost = cost + step;
+            match best.entry(next.clone()) {
+                Entry::Occupied(entry) if entry.get().0 <= cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert((cost, Some(state.clone())));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((cost, Some(state.clone())));
+                }
+            }
+            queue.push(Reverse((cost, next)));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cheapest_path() {
+        // 0 -> 1 -> 3 costs 2 + 2, the direct 0 -> 3 costs 5
+        let edges: &[(u32, u32, u64)] = &[(0, 1, 2), (0, 3, 5), (1, 2, 7), (1, 3, 2), (2, 3, 1)];
+        let neighbours = |&from: &u32| {
+            edges
+                .iter()
+                .filter(move |edge| edge.0 == from)
+                .map(|&(_, to, cost)| (to, cost))
+        };
+
+        assert_eq!(
+            dijkstra([0], neighbours, |&state| state == 3),
+            Some(Path {
+                cost: 4,
+                states: vec![0, 1, 3]
+            })
+        );
+        assert_eq!(dijkstra([0], neighbours, |&state| state == 4), None);
+    }
+
+    #[test]
+    fn test_several_starts() {
+        let path = dijkstra([10, 3], |&n: &u32| [(n + 1, 1)], |&n| n == 12).unwrap();
+        assert_eq!(path.cost, 2);
+        assert_eq!(path.states, vec![10, 11, 12]);
+    }
+}
@@ -0,0 +1,112 @@
+//! Line-by-line helpers for solving inputs too large to hold in memory.
+
+use std::io::{self, BufRead};
+use std::iter::Sum;
+use std::ops::Add;
+
+use crate::execution::map_sum;
+use crate::parsing::ParseError;
+
+/// Lines read before a batch is mapped
+const BATCH_LINES: usize = 4096;
+
+fn trim_newline(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// A line that failed to parse, as the `InvalidData` error of the read
+fn invalid_data(err: ParseError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Calls `f` on every line, reusing a single buffer, and stops at the first
+/// line `f` fails on
+pub fn for_each_line<R: BufRead>(
+    mut reader: R,
+    mut f: impl FnMut(&str) -> Result<(), ParseError>,
+) -> io::Result<()> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        f(trim_newline(&line)).map_err(invalid_data)?;
+    }
+}
+
+/// Sums `f` over every line. Lines are read in fixed size batches and each
+/// batch is mapped in parallel (unless running sequentially), so memory stays bounded by the batch.
+pub fn sum_lines<R, T, F>(mut reader: R, f: F) -> io::Result<T>
+where
+    R: BufRead,
+    T: Sum + Add<Output = T> + Send,
+    F: Fn(&str) -> Result<T, ParseError> + Sync,
+{
+    let mut total = T::sum(std::iter::empty());
+    let mut batch = vec![String::new(); BATCH_LINES];
+
+    loop {
+        let mut filled = 0;
+        while filled < BATCH_LINES {
+            batch[filled].clear();
+            if reader.read_line(&mut batch[filled])? == 0 {
+                break;
+            }
+            filled += 1;
+        }
+
+        let sum: Result<T, ParseError> = map_sum(&batch[..filled], |line| f(trim_newline(line)));
+        total = total + sum.map_err(invalid_data)?;
+
+        if filled < BATCH_LINES {
+            return Ok(total);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::parse_number;
+    use std::io::BufReader;
+
+    #[test]
+    fn test_sum_lines_across_batches() {
+        let input: String = (1..=BATCH_LINES * 2 + 3)
+            .map(|n| format!("{n}\r\n"))
+            .collect();
+        let reader = BufReader::with_capacity(7, input.as_bytes());
+        let expected = (1..=BATCH_LINES * 2 + 3).sum::<usize>();
+        assert_eq!(sum_lines(reader, parse_number::<usize>).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_for_each_line_matches_lines() {
+        let input = "a\n\nbc\r\nd";
+        let mut lines = Vec::new();
+        for_each_line(input.as_bytes(), |line| {
+            lines.push(line.to_string());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(lines, input.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_malformed_line_is_invalid_data() {
+        let err = sum_lines("1\n2\nthree\n4".as_bytes(), parse_number::<usize>).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "invalid number 'three'");
+
+        let mut seen = 0;
+        let err = for_each_line("1\nx\n3".as_bytes(), |line| {
+            seen += 1;
+            parse_number::<usize>(line).map(drop)
+        })
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(seen, 2);
+    }
+}
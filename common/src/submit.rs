@@ -0,0 +1,413 @@
+//! Posts answers to the puzzle server and remembers what it said. Every
+//! verdict goes into a JSON log, which is checked before anything is sent:
+//! answers that were already judged, or that fall outside the bounds left
+//! by earlier "too high"/"too low" verdicts, are answered from the log, and
+//! nothing is sent while the server asked us to wait.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::inputs::Remote;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong without a hint
+    Wrong,
+    /// Submitted too recently, try again after this many seconds
+    Wait(u64),
+    /// The part was already solved or is not unlocked yet
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Parses the article the server answers a submission with
+    pub fn parse(body: &str) -> Option<Verdict> {
+        if body.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if body.contains("You gave an answer too recently") {
+            Some(Verdict::Wait(wait_seconds(body).unwrap_or(60)))
+        } else if body.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if body.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else if body.contains("That's not the right answer") {
+            Some(Verdict::Wrong)
+        } else if body.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+/// Reads "You have 1m 5s left to wait" as 65
+fn wait_seconds(body: &str) -> Option<u64> {
+    let (_, rest) = body.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace().try_fold(0, |total, part| {
+        let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let value: u64 = value.parse().ok()?;
+        match unit {
+            "h" => Some(total + value * 3600),
+            "m" => Some(total + value * 60),
+            "s" => Some(total + value),
+            _ => None,
+        }
+    })
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(seconds) => write!(f, "wait {seconds}s"),
+            Verdict::WrongLevel => write!(f, "already solved or locked"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Log(PathBuf, io::Error),
+    LogFormat(PathBuf, serde_json::Error),
+    Transport(String),
+    Http(u16, String),
+    /// The server answered with a page we don't understand
+    UnexpectedResponse(String),
+    /// Still inside a wait the server asked for, nothing was sent
+    RateLimited {
+        seconds: u64,
+    },
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Log(path, e) => write!(f, "{}: {e}", path.display()),
+            SubmitError::LogFormat(path, e) => write!(f, "{}: {e}", path.display()),
+            SubmitError::Transport(e) => write!(f, "request failed: {e}"),
+            SubmitError::Http(status, body) => {
+                write!(f, "server answered {status}: {}", body.trim())
+            }
+            SubmitError::UnexpectedResponse(body) => {
+                write!(f, "unexpected response: {}", body.trim())
+            }
+            SubmitError::RateLimited { seconds } => {
+                write!(f, "rate limited, try again in {seconds}s")
+            }
+        }
+    }
+}
+
+impl Error for SubmitError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Unix time the answer was submitted at
+    pub submitted_at: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SubmissionLog {
+    pub entries: Vec<Entry>,
+    /// Unix time before which nothing gets submitted
+    pub retry_after: Option<u64>,
+}
+
+impl SubmissionLog {
+    /// Loads the log, a missing file is an empty log
+    pub fn load(path: &Path) -> Result<Self, SubmitError> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| SubmitError::LogFormat(path.to_path_buf(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(SubmissionLog::default()),
+            Err(e) => Err(SubmitError::Log(path.to_path_buf(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SubmitError> {
+        let json = serde_json::to_string_pretty(self).expect("log is always serializable");
+        fs::write(path, json + "\n").map_err(|e| SubmitError::Log(path.to_path_buf(), e))
+    }
+
+    fn entries(&self, year: u32, day: u32, part: u32) -> impl Iterator<Item = &Entry> {
+        self.entries
+            .iter()
+            .filter(move |entry| (entry.year, entry.day, entry.part) == (year, day, part))
+    }
+
+    /// Exclusive bounds left by earlier "too low" and "too high" verdicts
+    pub fn bounds(&self, year: u32, day: u32, part: u32) -> (Option<i128>, Option<i128>) {
+        let judged = |verdict| {
+            self.entries(year, day, part)
+                .filter(move |entry| entry.verdict == verdict)
+                .filter_map(|entry| entry.answer.parse::<i128>().ok())
+        };
+        (
+            judged(Verdict::TooLow).max(),
+            judged(Verdict::TooHigh).min(),
+        )
+    }
+
+    /// What the log already says about `answer`, if anything
+    pub fn known_verdict(&self, year: u32, day: u32, part: u32, answer: &str) -> Option<Verdict> {
+        if let Some(correct) = self
+            .entries(year, day, part)
+            .find(|entry| entry.verdict == Verdict::Correct)
+        {
+            return Some(if correct.answer == answer {
+                Verdict::Correct
+            } else {
+                Verdict::Wrong
+            });
+        }
+
+        if let Some(entry) = self.entries(year, day, part).find(|entry| {
+            entry.answer == answer
+                && matches!(
+                    entry.verdict,
+                    Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong
+                )
+        }) {
+            return Some(entry.verdict.clone());
+        }
+
+        let value = answer.parse::<i128>().ok()?;
+        match self.bounds(year, day, part) {
+            (Some(low), _) if value <= low => Some(Verdict::TooLow),
+            (_, Some(high)) if value >= high => Some(Verdict::TooHigh),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub verdict: Verdict,
+    /// The verdict came from the log and nothing was sent
+    pub from_log: bool,
+}
+
+pub struct Submitter {
+    pub year: u32,
+    pub remote: Remote,
+    pub log_path: PathBuf,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+impl Submitter {
+    pub fn new(year: u32, remote: Remote, log_path: &Path) -> Self {
+        Submitter {
+            year,
+            remote,
+            log_path: log_path.to_path_buf(),
+        }
+    }
+
+    pub fn submit(&self, day: u32, part: u32, answer: &str) -> Result<Submission, SubmitError> {
+        let mut log = SubmissionLog::load(&self.log_path)?;
+
+        if let Some(verdict) = log.known_verdict(self.year, day, part, answer) {
+            return Ok(Submission {
+                verdict,
+                from_log: true,
+            });
+        }
+
+        let submitted_at = now();
+        if let Some(retry_after) = log.retry_after.filter(|&after| after > submitted_at) {
+            return Err(SubmitError::RateLimited {
+                seconds: retry_after - submitted_at,
+            });
+        }
+
+        let url = format!("{}/{}/day/{day}/answer", self.remote.base_url, self.year);
+        let level = part.to_string();
+        let response = self
+            .remote
+            .client
+            .post_form(
+                &url,
+                &self.remote.cookie(),
+                &[("level", &level), ("answer", answer)],
+            )
+            .map_err(SubmitError::Transport)?;
+        if response.status != 200 {
+            return Err(SubmitError::Http(response.status, response.body));
+        }
+        let verdict =
+            Verdict::parse(&response.body).ok_or(SubmitError::UnexpectedResponse(response.body))?;
+
+        match verdict {
+            Verdict::Wait(seconds) => log.retry_after = Some(submitted_at + seconds),
+            // Only judged answers go into the log
+            Verdict::WrongLevel => {}
+            _ => log.entries.push(Entry {
+                year: self.year,
+                day,
+                part,
+                answer: answer.to_string(),
+                verdict: verdict.clone(),
+                submitted_at,
+            }),
+        }
+        log.save(&self.log_path)?;
+
+        Ok(Submission {
+            verdict,
+            from_log: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::test_server;
+    use std::env;
+
+    static TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+        If you're stuck, make sure you're using the full input data. \
+        Please wait one minute before trying again.</p></article>";
+    static TOO_LOW: &str =
+        "<article><p>That's not the right answer; your answer is too low.</p></article>";
+    static CORRECT: &str = "<article><p>That's the right answer! You are one gold star closer \
+        to restoring snow operations.</p></article>";
+    static TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait \
+        after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>";
+
+    fn log_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-submit-{name}-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(Verdict::parse(TOO_HIGH), Some(Verdict::TooHigh));
+        assert_eq!(Verdict::parse(TOO_LOW), Some(Verdict::TooLow));
+        assert_eq!(Verdict::parse(CORRECT), Some(Verdict::Correct));
+        assert_eq!(Verdict::parse(TOO_RECENT), Some(Verdict::Wait(65)));
+        assert_eq!(Verdict::parse("<html></html>"), None);
+    }
+
+    #[test]
+    fn test_bounds_narrow_and_log_prevents_resubmission() {
+        let responses = [TOO_HIGH, TOO_LOW, CORRECT];
+        let (base_url, requests) =
+            test_server::serve(responses.iter().map(|r| (200, r.to_string())).collect());
+        let path = log_path("bounds");
+        let submitter = Submitter::new(2023, Remote::new(&base_url, "abc"), &path);
+
+        let sent = |verdict| Submission {
+            verdict,
+            from_log: false,
+        };
+        let known = |verdict| Submission {
+            verdict,
+            from_log: true,
+        };
+
+        assert_eq!(
+            submitter.submit(7, 1, "500").unwrap(),
+            sent(Verdict::TooHigh)
+        );
+        let request = requests.recv().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/2023/day/7/answer");
+        assert_eq!(request.body, "level=1&answer=500");
+
+        assert_eq!(
+            submitter.submit(7, 1, "500").unwrap(),
+            known(Verdict::TooHigh)
+        );
+        assert_eq!(
+            submitter.submit(7, 1, "600").unwrap(),
+            known(Verdict::TooHigh)
+        );
+        assert_eq!(
+            submitter.submit(7, 1, "100").unwrap(),
+            sent(Verdict::TooLow)
+        );
+        assert_eq!(
+            submitter.submit(7, 1, "99").unwrap(),
+            known(Verdict::TooLow)
+        );
+        assert_eq!(
+            submitter.submit(7, 1, "250").unwrap(),
+            sent(Verdict::Correct)
+        );
+        assert_eq!(
+            submitter.submit(7, 1, "251").unwrap(),
+            known(Verdict::Wrong)
+        );
+        assert_eq!(
+            submitter.submit(7, 1, "250").unwrap(),
+            known(Verdict::Correct)
+        );
+
+        let log = SubmissionLog::load(&path).unwrap();
+        assert_eq!(log.entries.len(), 3);
+        assert_eq!(log.bounds(2023, 7, 1), (Some(100), Some(500)));
+        assert_eq!(log.bounds(2023, 7, 2), (None, None));
+    }
+
+    #[test]
+    fn test_wait_is_respected_locally() {
+        let (base_url, _requests) = test_server::serve(vec![(200, TOO_RECENT.to_string())]);
+        let path = log_path("wait");
+        let submitter = Submitter::new(2023, Remote::new(&base_url, "abc"), &path);
+
+        assert_eq!(
+            submitter.submit(1, 2, "42").unwrap().verdict,
+            Verdict::Wait(65)
+        );
+        // The server only had one response, this one must not reach it
+        assert!(matches!(
+            submitter.submit(1, 2, "42"),
+            Err(SubmitError::RateLimited { seconds }) if seconds <= 65
+        ));
+        assert!(SubmissionLog::load(&path).unwrap().entries.is_empty());
+    }
+
+    #[test]
+    fn test_unexpected_response() {
+        let (base_url, _requests) = test_server::serve(vec![
+            (200, "<html>maintenance</html>".to_string()),
+            (500, "oops".to_string()),
+        ]);
+        let path = log_path("unexpected");
+        let submitter = Submitter::new(2023, Remote::new(&base_url, "abc"), &path);
+
+        assert!(matches!(
+            submitter.submit(3, 1, "1"),
+            Err(SubmitError::UnexpectedResponse(_))
+        ));
+        assert!(matches!(
+            submitter.submit(3, 1, "1"),
+            Err(SubmitError::Http(500, _))
+        ));
+    }
+}
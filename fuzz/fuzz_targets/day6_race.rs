@@ -0,0 +1,10 @@
+#![no_main]
+
+use advent_of_code_2023::fuzzing;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = fuzzing::day6_race(input);
+    }
+});
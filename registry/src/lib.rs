@@ -0,0 +1,305 @@
+//! Build-time discovery of a year's solutions. A year crate calls
+//! [`generate`] from its `build.rs`, which reads the `mod dayN;` declarations
+//! in `src/lib.rs`, collects every `#[aoc(dayN, partN[, Name])]` function in
+//! those modules and writes them out as a `SOLUTIONS` table for the runner.
+//!
+//! Solutions have to take the raw `&str` input, like all of them do. A day
+//! module may also have a `pub fn parse(input: &str) -> Result<_, ParseError>`,
+//! the parse step its solutions start with, which the runner then times on
+//! its own.
+//!
+//! The binary's `build.rs` calls [`generate_years`] the same way, to list
+//! the year crates under `years/` in a `YEARS` table.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u32,
+    pub part: u32,
+    pub variant: Option<String>,
+    pub function: String,
+    /// Whether the day module has a `pub fn parse` step
+    pub parse: bool,
+}
+
+/// Day numbers of the `mod dayN;` declarations in a `lib.rs`
+pub fn declared_days(lib: &str) -> Vec<u32> {
+    let mut days: Vec<u32> = lib
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix("pub ").unwrap_or(line);
+            line.strip_prefix("mod day")?
+                .strip_suffix(';')?
+                .parse()
+                .ok()
+        })
+        .collect();
+    days.sort_unstable();
+    days
+}
+
+/// The `#[aoc]` solutions in the source of a day module, in source order
+pub fn solutions(source: &str) -> Vec<Entry> {
+    let parse = source.lines().any(|line| line.starts_with("pub fn parse("));
+    let mut entries = Vec::new();
+    let mut lines = source.lines().map(str::trim);
+
+    while let Some(line) = lines.next() {
+        let Some(args) = line
+            .strip_prefix("#[aoc(")
+            .and_then(|args| args.strip_suffix(")]"))
+        else {
+            continue;
+        };
+        let args: Vec<&str> = args.split(',').map(str::trim).collect();
+        let (Some(day), Some(part)) = (
+            args.first()
+                .and_then(|day| day.strip_prefix("day")?.parse().ok()),
+            args.get(1)
+                .and_then(|part| part.strip_prefix("part")?.parse().ok()),
+        ) else {
+            panic!("malformed attribute: {line}");
+        };
+
+        let function = lines
+            .find_map(|line| line.strip_prefix("pub fn "))
+            .and_then(|signature| signature.split(['(', '<']).next())
+            .unwrap_or_else(|| panic!("no `pub fn` after {line}"));
+
+        entries.push(Entry {
+            day,
+            part,
+            variant: args.get(2).map(|name| name.to_string()),
+            function: function.to_string(),
+            parse,
+        });
+    }
+
+    entries
+}
+
+/// Renders the `SOLUTIONS` table
+pub fn render(entries: &[Entry]) -> String {
+    let mut out = String::from("pub static SOLUTIONS: &[aoc_common::runner::Solution] = &[\n");
+    for entry in entries {
+        let variant = match &entry.variant {
+            Some(variant) => format!("Some({variant:?})"),
+            None => "None".to_string(),
+        };
+        let parse = if entry.parse {
+            format!(", crate::day{}::parse", entry.day)
+        } else {
+            String::new()
+        };
+        writeln!(
+            out,
+            "    aoc_common::solution!({}, {}, {variant}, crate::day{}::{}{parse}),",
+            entry.day, entry.part, entry.day, entry.function
+        )
+        .unwrap();
+    }
+    out.push_str("];\n");
+    out
+}
+
+/// Writes `$OUT_DIR/solutions.rs` for the crate being built, to be pulled in
+/// with `include!`
+pub fn generate() {
+    let src = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+
+    let lib_path = src.join("lib.rs");
+    let lib =
+        fs::read_to_string(&lib_path).unwrap_or_else(|e| panic!("{}: {e}", lib_path.display()));
+
+    let mut entries = Vec::new();
+    for day in declared_days(&lib) {
+        let path = src.join(format!("day{day}.rs"));
+        println!("cargo:rerun-if-changed={}", path.display());
+        let source =
+            fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        let mut day_entries = solutions(&source);
+        // Parts in order, variants in source order
+        day_entries.sort_by_key(|entry| entry.part);
+        entries.extend(day_entries);
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(&out, render(&entries)).unwrap_or_else(|e| panic!("{}: {e}", out.display()));
+}
+
+/// A year crate under `years/`
+#[derive(Debug, PartialEq, Eq)]
+pub struct YearCrate {
+    pub year: u32,
+    /// The package name as code refers to it, `advent_of_code_2023`
+    pub name: String,
+}
+
+/// The package name of a `Cargo.toml`, with dashes turned into underscores
+pub fn crate_name(manifest: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if in_package && key.trim() == "name" {
+            return Some(value.trim().trim_matches('"').replace('-', "_"));
+        }
+    }
+    None
+}
+
+/// Renders the `YEARS` table, each year's `REGISTRY` in year order
+pub fn render_years(years: &[YearCrate]) -> String {
+    let mut out = String::from("static YEARS: &[&aoc_common::runner::Year] = &[\n");
+    for year in years {
+        writeln!(out, "    &{}::REGISTRY,", year.name).unwrap();
+    }
+    out.push_str("];\n");
+    out
+}
+
+/// Writes `$OUT_DIR/years.rs` for the binary, listing every `years/<year>`
+/// crate. Each one still has to be a dependency in the root `Cargo.toml`,
+/// which Cargo cannot be told from here, so a missing one fails the build
+/// with the line to add.
+pub fn generate_years() {
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).to_path_buf();
+    let years_dir = root.join("years");
+    let manifest_path = root.join("Cargo.toml");
+    println!("cargo:rerun-if-changed={}", years_dir.display());
+    println!("cargo:rerun-if-changed={}", manifest_path.display());
+
+    let manifest = fs::read_to_string(&manifest_path)
+        .unwrap_or_else(|e| panic!("{}: {e}", manifest_path.display()));
+    let entries =
+        fs::read_dir(&years_dir).unwrap_or_else(|e| panic!("{}: {e}", years_dir.display()));
+
+    let mut years = Vec::new();
+    for entry in entries {
+        let dir = entry
+            .unwrap_or_else(|e| panic!("{}: {e}", years_dir.display()))
+            .path();
+        let Some(year) = dir.file_name().and_then(|name| name.to_str()?.parse().ok()) else {
+            continue;
+        };
+        let year_manifest = dir.join("Cargo.toml");
+        let Ok(year_manifest) = fs::read_to_string(year_manifest) else {
+            continue;
+        };
+        let name = crate_name(&year_manifest)
+            .unwrap_or_else(|| panic!("no package name in {}/Cargo.toml", dir.display()));
+
+        if !manifest.contains(&format!("path = \"years/{year}\"")) {
+            panic!(
+                "years/{year} is not a dependency of the binary, add \
+                 `{} = {{ path = \"years/{year}\" }}` to {}",
+                name.replace('_', "-"),
+                manifest_path.display()
+            );
+        }
+        years.push(YearCrate { year, name });
+    }
+    years.sort_by_key(|year| year.year);
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("years.rs");
+    fs::write(&out, render_years(&years)).unwrap_or_else(|e| panic!("{}: {e}", out.display()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = r#"use std::str::FromStr;
+
+#[aoc(day4, part2, Iterative)]
+pub fn part2_iterative(input: &str) -> u32 {
+    0
+}
+
+#[aoc(day4, part1)]
+#[allow(unused)]
+pub fn part1(input: &str) -> u32 {
+    0
+}
+
+fn helper() {}
+"#;
+
+    #[test]
+    fn test_declared_days() {
+        let lib = "mod day10;\nmod day2;\npub mod day3;\npub mod execution;\nmod days;";
+        assert_eq!(declared_days(lib), vec![2, 3, 10]);
+    }
+
+    #[test]
+    fn test_solutions() {
+        assert_eq!(
+            solutions(SAMPLE),
+            vec![
+                Entry {
+                    day: 4,
+                    part: 2,
+                    variant: Some("Iterative".to_string()),
+                    function: "part2_iterative".to_string(),
+                    parse: false,
+                },
+                Entry {
+                    day: 4,
+                    part: 1,
+                    variant: None,
+                    function: "part1".to_string(),
+                    parse: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = render(&solutions(SAMPLE));
+        assert!(rendered.contains(
+            "aoc_common::solution!(4, 2, Some(\"Iterative\"), crate::day4::part2_iterative),"
+        ));
+        assert!(rendered.contains("aoc_common::solution!(4, 1, None, crate::day4::part1),"));
+
+        let source =
+            format!("{SAMPLE}\npub fn parse(input: &str) -> Result<u32, ParseError> {{}}\n");
+        assert!(render(&solutions(&source)).contains(
+            "aoc_common::solution!(4, 1, None, crate::day4::part1, crate::day4::parse),"
+        ));
+    }
+
+    #[test]
+    fn test_crate_name() {
+        let manifest = "[package]\nname = \"advent-of-code-2024\"\nversion = \"0.1.0\"\n\n\
+                        [dependencies]\nname = \"not-this\"\n";
+        assert_eq!(crate_name(manifest).as_deref(), Some("advent_of_code_2024"));
+        assert_eq!(crate_name("[dependencies]\nname = \"x\"\n"), None);
+    }
+
+    #[test]
+    fn test_render_years() {
+        let years = [2023, 2024].map(|year| YearCrate {
+            year,
+            name: format!("advent_of_code_{year}"),
+        });
+        assert_eq!(
+            render_years(&years),
+            "static YEARS: &[&aoc_common::runner::Year] = &[\n    \
+             &advent_of_code_2023::REGISTRY,\n    \
+             &advent_of_code_2024::REGISTRY,\n\
+             ];\n"
+        );
+    }
+}
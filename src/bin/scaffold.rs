@@ -0,0 +1,43 @@
+use aoc_common::scaffold::scaffold;
+use clap::Parser;
+use std::path::PathBuf;
+use std::process;
+
+/// Creates the module, tests and input directory for a new day
+#[derive(Debug, Parser)]
+struct Args {
+    /// Day to create
+    #[arg(long)]
+    day: u32,
+
+    /// Year crate to add it to, under `years/`
+    #[arg(long)]
+    year: u32,
+
+    /// Directory holding a `<year>/dayN.txt` tree of inputs
+    #[arg(long, default_value = "input")]
+    inputs: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+    let crate_dir = PathBuf::from("years").join(args.year.to_string());
+    let input_dir = args.inputs.join(args.year.to_string());
+
+    match scaffold(&crate_dir, &input_dir, args.day) {
+        Ok(created) => {
+            println!("created {}", created.module.display());
+            println!("registered in {}", created.lib.display());
+            println!(
+                "input goes to {}, or download it with `aoc --year {} --day {} --fetch`",
+                created.input.display(),
+                args.year,
+                args.day
+            );
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
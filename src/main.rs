@@ -1,7 +1,175 @@
-extern crate advent_of_code_2023;
-extern crate aoc_runner;
-extern crate aoc_runner_derive;
+use aoc_common::execution::Execution;
+use aoc_common::inputs::{InputStore, Remote, DEFAULT_BASE_URL};
+use aoc_common::runner::{self, Filter};
+use aoc_common::submit::Submitter;
+use clap::{Parser, ValueEnum};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::process;
 
-use aoc_runner_derive::aoc_main;
+// Every year crate under `years/`, listed by `build.rs`, each of them
+// collecting its own solutions
+include!(concat!(env!("OUT_DIR"), "/years.rs"));
 
-aoc_main! { lib = advent_of_code_2023 }
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Table,
+    Json,
+}
+
+/// Runs the Advent of Code solutions and reports answers and timings
+#[derive(Debug, Parser)]
+struct Args {
+    /// Year to run, by default the latest one with an input directory
+    #[arg(long)]
+    year: Option<u32>,
+
+    /// Only run this day
+    #[arg(long)]
+    day: Option<u32>,
+
+    /// Only run this part
+    #[arg(long)]
+    part: Option<u32>,
+
+    /// Only run the variant with this name
+    #[arg(long)]
+    variant: Option<String>,
+
+    /// Directory holding a `<year>/dayN.txt` tree of inputs
+    #[arg(long, default_value = "input")]
+    inputs: PathBuf,
+
+    /// Directory holding the `dayN.txt` inputs, `<inputs>/<year>` by default
+    #[arg(long)]
+    input_dir: Option<PathBuf>,
+
+    #[arg(long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+
+    /// Run the parallel solvers on a pool with this many threads
+    #[arg(long, conflicts_with = "sequential")]
+    threads: Option<NonZeroUsize>,
+
+    /// Run every solver on a single thread, without rayon
+    #[arg(long)]
+    sequential: bool,
+
+    /// Download inputs missing from the input directory before running
+    #[arg(long, requires = "session")]
+    fetch: bool,
+
+    /// Session cookie used to download inputs
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+
+    /// Server to download inputs from and submit answers to
+    #[arg(long, default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+
+    /// Submit the answer for `--day` and `--part` after running it
+    #[arg(long, requires_all = ["day", "part", "session"])]
+    submit: bool,
+
+    /// Log of submitted answers, defaults to `submissions.json` in the
+    /// input directory
+    #[arg(long)]
+    submission_log: Option<PathBuf>,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let year = match args.year {
+        Some(number) => match YEARS.iter().find(|year| year.year == number) {
+            Some(year) => year,
+            None => {
+                let known: Vec<String> = YEARS.iter().map(|year| year.year.to_string()).collect();
+                eprintln!(
+                    "no solutions for {number}, known years: {}",
+                    known.join(", ")
+                );
+                process::exit(1);
+            }
+        },
+        None => YEARS
+            .iter()
+            .max_by_key(|year| (args.inputs.join(year.year.to_string()).is_dir(), year.year))
+            .expect("at least one year"),
+    };
+    let input_dir = args
+        .input_dir
+        .unwrap_or_else(|| args.inputs.join(year.year.to_string()));
+    let filter = Filter {
+        day: args.day,
+        part: args.part,
+        variant: args.variant,
+    };
+
+    let execution = match (args.sequential, args.threads) {
+        (true, _) => Execution::Sequential,
+        (false, Some(threads)) => Execution::Threads(threads.get()),
+        (false, None) => Execution::Parallel,
+    };
+
+    if args.fetch {
+        let session = args.session.as_deref().unwrap_or_default();
+        let store = InputStore::new(year.year, &input_dir)
+            .with_remote(Remote::new(&args.base_url, session));
+        for day in filter.days(year) {
+            if let Err(e) = store.resolve(day) {
+                eprintln!("day {day}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let report = runner::run(year, &filter, &input_dir, execution);
+    match args.format {
+        Format::Table => print!("{report}"),
+        Format::Json => println!("{}", report.to_json()),
+    }
+
+    if args.submit {
+        let (day, part) = (args.day.unwrap_or_default(), args.part.unwrap_or_default());
+        let answer = match report.answer(day, part) {
+            Ok(answer) => answer,
+            Err(e) => {
+                eprintln!("not submitting: {e}");
+                process::exit(1);
+            }
+        };
+
+        let session = args.session.as_deref().unwrap_or_default();
+        let log_path = args
+            .submission_log
+            .unwrap_or_else(|| input_dir.join("submissions.json"));
+        let submitter = Submitter::new(year.year, Remote::new(&args.base_url, session), &log_path);
+        match submitter.submit(day, part, answer) {
+            Ok(submission) if submission.from_log => {
+                eprintln!(
+                    "{answer}: {} (from the log, not submitted)",
+                    submission.verdict
+                )
+            }
+            Ok(submission) => eprintln!("{answer}: {}", submission.verdict),
+            Err(e) => {
+                eprintln!("submitting {answer}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threads_must_be_positive() {
+        let threads = |value| Args::try_parse_from(["aoc", "--threads", value]);
+        assert_eq!(threads("4").unwrap().threads, NonZeroUsize::new(4));
+        assert!(threads("0").is_err());
+        assert!(threads("-1").is_err());
+    }
+}
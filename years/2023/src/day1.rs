@@ -1,3 +1,7 @@
+use std::io::{self, BufRead};
+
+use aoc_common::streaming::sum_lines;
+
 #[aoc(day1, part1, FirstSolutionButProbablyBad)]
 pub fn part1_bad(input: &str) -> u32 {
     input
@@ -15,24 +19,23 @@ pub fn part1_bad(input: &str) -> u32 {
 
 #[aoc(day1, part1, Maybe)]
 pub fn part1_maybe(input: &str) -> u32 {
-    input
-        .lines()
-        .filter_map(|line| {
-            let first_digit = line.chars().find(|c| c.is_ascii_digit());
-            let last_digit = line.chars().rev().find(|c| c.is_ascii_digit());
+    part1_stream(input.as_bytes()).unwrap()
+}
 
-            match (first_digit, last_digit) {
-                (Some(f), Some(l)) if f != l => {
-                    Some(f.to_digit(10).unwrap() * 10 + l.to_digit(10).unwrap())
-                }
-                (Some(d), _) | (_, Some(d)) => {
-                    let digit = d.to_digit(10).unwrap();
-                    Some(digit * 10 + digit)
-                }
-                _ => None,
+pub fn part1_stream<R: BufRead>(reader: R) -> io::Result<u32> {
+    sum_lines(reader, |line| {
+        let first_digit = line.chars().find(|c| c.is_ascii_digit());
+        let last_digit = line.chars().rev().find(|c| c.is_ascii_digit());
+
+        Ok(match (first_digit, last_digit) {
+            (Some(f), Some(l)) if f != l => f.to_digit(10).unwrap() * 10 + l.to_digit(10).unwrap(),
+            (Some(d), _) | (_, Some(d)) => {
+                let digit = d.to_digit(10).unwrap();
+                digit * 10 + digit
             }
+            _ => 0,
         })
-        .sum()
+    })
 }
 
 #[aoc(day1, part1, HorribleShit)]
@@ -105,43 +108,45 @@ impl Number {
 
 #[aoc(day1, part2, FirstSolutionButProbablyBad)]
 pub fn part2_first(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            let mut first_digit = String::new();
-            let mut first_digit_value = '0';
-            for c in line.chars() {
-                first_digit.push(c);
-                let number = Number::from(&first_digit);
-                if number.is_some() {
-                    first_digit_value = number.unwrap().as_digit_char();
-                    break;
-                }
+    part2_stream(input.as_bytes()).unwrap()
+}
+
+pub fn part2_stream<R: BufRead>(reader: R) -> io::Result<u32> {
+    sum_lines(reader, |line| {
+        let mut first_digit = String::new();
+        let mut first_digit_value = '0';
+        for c in line.chars() {
+            first_digit.push(c);
+            if let Some(number) = Number::from(&first_digit) {
+                first_digit_value = number.as_digit_char();
+                break;
             }
+        }
 
-            let mut last_digit = String::new();
-            let mut last_digit_value = '0';
-            for c in line.chars().rev() {
-                last_digit.push(c);
-                let last_digit = last_digit.chars().rev().collect::<String>();
-                let number = Number::from(&last_digit);
-                if number.is_some() {
-                    last_digit_value = number.unwrap().as_digit_char();
-                    break;
-                }
+        let mut last_digit = String::new();
+        let mut last_digit_value = '0';
+        for c in line.chars().rev() {
+            last_digit.push(c);
+            let last_digit = last_digit.chars().rev().collect::<String>();
+            if let Some(number) = Number::from(&last_digit) {
+                last_digit_value = number.as_digit_char();
+                break;
             }
+        }
 
-            let mut number = String::new();
-            number.push(first_digit_value);
-            number.push(last_digit_value);
-            number.parse::<u32>().unwrap()
-        })
-        .sum::<u32>()
+        let mut number = String::new();
+        number.push(first_digit_value);
+        number.push(last_digit_value);
+        Ok(number.parse::<u32>().unwrap())
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{part1_bad, part1_maybe, part1_shit, part2_first};
+    use super::{part1_bad, part1_maybe, part1_shit, part2_first, part2_stream};
+    use crate::generators;
+    use proptest::prelude::*;
+    use std::io::BufReader;
 
     static SAMPLE_1: &str = r#"1abc2
     pqr3stu8vwx
@@ -174,4 +179,20 @@ mod tests {
     fn test_part_2_first() {
         assert_eq!(part2_first(SAMPLE_2), 281);
     }
+
+    #[test]
+    fn test_part_2_stream() {
+        let reader = BufReader::with_capacity(6, SAMPLE_2.as_bytes());
+        assert_eq!(part2_stream(reader).unwrap(), 281);
+    }
+
+    proptest! {
+        #[test]
+        fn test_part_1_variants_agree(seed: u64, lines in 1usize..100, max_filler in 0usize..8) {
+            let input = generators::calibration_document(seed, lines, max_filler);
+            let expected = part1_bad(&input);
+            prop_assert_eq!(part1_maybe(&input), expected);
+            prop_assert_eq!(part1_shit(&input), expected);
+        }
+    }
 }
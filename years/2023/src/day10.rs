@@ -0,0 +1,257 @@
+use std::{fmt, str::FromStr};
+
+use aoc_common::geometry::{interior_points, shoelace_area2};
+use aoc_common::grid::{Direction, Grid, Pos};
+use aoc_common::parsing::ParseError;
+
+/// Bitmask of the sides a tile connects, bit `Direction::index()` per side
+fn connections(tile: u8) -> Result<u8, ParseError> {
+    let sides: &[Direction] = match tile {
+        b'|' => &[Direction::Up, Direction::Down],
+        b'-' => &[Direction::Left, Direction::Right],
+        b'L' => &[Direction::Up, Direction::Right],
+        b'J' => &[Direction::Up, Direction::Left],
+        b'7' => &[Direction::Down, Direction::Left],
+        b'F' => &[Direction::Down, Direction::Right],
+        b'.' | b'S' => &[],
+        _ => return Err(ParseError::InvalidChar(tile as char)),
+    };
+    Ok(sides.iter().fold(0, |mask, side| mask | 1 << side.index()))
+}
+
+fn connects(mask: u8, direction: Direction) -> bool {
+    mask & 1 << direction.index() != 0
+}
+
+#[derive(Debug)]
+pub(crate) struct Maze {
+    /// Connections of every tile, the start's worked out from its neighbours
+    pipes: Grid<u8>,
+    /// The loop from the start back to it, every tile once
+    path: Vec<Pos>,
+}
+
+impl FromStr for Maze {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = Grid::parse(s)?;
+        let start = tiles
+            .position(|&tile| tile == b'S')
+            .ok_or(ParseError::Missing("start tile 'S'"))?;
+
+        let mut pipes = Grid::new(tiles.width(), tiles.height(), 0);
+        for (pos, &tile) in tiles.iter() {
+            pipes[pos] = connections(tile)?;
+        }
+        pipes[start] = tiles
+            .neighbours(start)
+            .filter(|&(direction, next)| connects(pipes[next], direction.opposite()))
+            .fold(0, |mask, (direction, _)| mask | 1 << direction.index());
+        if pipes[start].count_ones() != 2 {
+            return Err(ParseError::Missing("two pipes connected to the start"));
+        }
+
+        let mut path = Vec::new();
+        let mut pos = start;
+        let mut heading = Direction::ALL
+            .into_iter()
+            .find(|&direction| connects(pipes[start], direction))
+            .unwrap();
+        loop {
+            pos = pipes
+                .step(pos, heading)
+                .filter(|&next| connects(pipes[next], heading.opposite()))
+                .ok_or(ParseError::Missing("closed loop"))?;
+            path.push(pos);
+            if pos == start {
+                break;
+            }
+            let came_from = heading.opposite();
+            heading = Direction::ALL
+                .into_iter()
+                .find(|&direction| direction != came_from && connects(pipes[pos], direction))
+                .unwrap();
+        }
+
+        Ok(Maze { pipes, path })
+    }
+}
+
+impl Maze {
+    fn on_loop(&self) -> Grid<bool> {
+        let mut on_loop = Grid::new(self.pipes.width(), self.pipes.height(), false);
+        for &pos in &self.path {
+            on_loop[pos] = true;
+        }
+        on_loop
+    }
+
+    /// Tiles inside the loop, found row by row: a ray from the left edge is
+    /// inside after crossing an odd number of loop tiles that connect upwards
+    fn enclosed(&self) -> Grid<bool> {
+        let on_loop = self.on_loop();
+        let mut enclosed = Grid::new(self.pipes.width(), self.pipes.height(), false);
+        let mut inside = false;
+
+        for pos in self.pipes.positions() {
+            if pos.x == 0 {
+                inside = false;
+            }
+            if on_loop[pos] {
+                inside ^= connects(self.pipes[pos], Direction::Up);
+            } else {
+                enclosed[pos] = inside;
+            }
+        }
+
+        enclosed
+    }
+}
+
+/// The loop in box drawing characters, enclosed tiles as `I`
+impl fmt::Display for Maze {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let on_loop = self.on_loop();
+        let enclosed = self.enclosed();
+        let up = 1 << Direction::Up.index();
+        let right = 1 << Direction::Right.index();
+        let down = 1 << Direction::Down.index();
+        let left = 1 << Direction::Left.index();
+
+        let rendered = self.pipes.render(|pos, &mask| {
+            if !on_loop[pos] {
+                return if enclosed[pos] { 'I' } else { ' ' };
+            }
+            match mask {
+                m if m == up | down => '│',
+                m if m == left | right => '─',
+                m if m == up | right => '└',
+                m if m == up | left => '┘',
+                m if m == down | left => '┐',
+                m if m == down | right => '┌',
+                _ => unreachable!("loop tiles connect two sides"),
+            }
+        });
+        f.write_str(&rendered)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Maze, ParseError> {
+    Maze::from_str(input)
+}
+
+pub fn render(input: &str) -> String {
+    parse(input).unwrap().to_string()
+}
+
+#[aoc(day10, part1)]
+pub fn farthest_point(input: &str) -> usize {
+    let maze = parse(input).unwrap();
+
+    maze.path.len() / 2
+}
+
+#[aoc(day10, part2, Shoelace)]
+pub fn enclosed_tiles_shoelace(input: &str) -> i64 {
+    let maze = parse(input).unwrap();
+    let vertices: Vec<(i64, i64)> = maze
+        .path
+        .iter()
+        .map(|pos| (pos.x as i64, pos.y as i64))
+        .collect();
+
+    // Every loop tile is a lattice point on the boundary
+    interior_points(shoelace_area2(&vertices), maze.path.len() as i64)
+}
+
+#[aoc(day10, part2, Scanline)]
+pub fn enclosed_tiles_scanline(input: &str) -> usize {
+    let maze = parse(input).unwrap();
+
+    maze.enclosed().iter().filter(|(_, &inside)| inside).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE_1: &str = r#".....
+.S-7.
+.|.|.
+.L-J.
+....."#;
+
+    static SAMPLE_2: &str = r#"7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ"#;
+
+    static SAMPLE_3: &str = r#"...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+..........."#;
+
+    static SAMPLE_4: &str = r#".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ..."#;
+
+    static SAMPLE_5: &str = r#"FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L"#;
+
+    #[test]
+    fn test_farthest_point() {
+        assert_eq!(farthest_point(SAMPLE_1), 4);
+        assert_eq!(farthest_point(SAMPLE_2), 8);
+    }
+
+    #[test]
+    fn test_enclosed_tiles() {
+        for (sample, expected) in [(SAMPLE_1, 1), (SAMPLE_3, 4), (SAMPLE_4, 8), (SAMPLE_5, 10)] {
+            assert_eq!(enclosed_tiles_shoelace(sample), expected);
+            assert_eq!(enclosed_tiles_scanline(sample), expected as usize);
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(render(SAMPLE_1), "     \n ┌─┐ \n │I│ \n └─┘ \n     \n");
+    }
+
+    #[test]
+    fn test_broken_loop_is_an_error() {
+        assert_eq!(
+            Maze::from_str(".....\n.S-7.\n.|.|.\n.L-..\n.....").unwrap_err(),
+            ParseError::Missing("closed loop")
+        );
+        assert_eq!(
+            Maze::from_str("S-.\n...").unwrap_err(),
+            ParseError::Missing("two pipes connected to the start")
+        );
+        assert_eq!(
+            Maze::from_str("F7\nLJ").unwrap_err(),
+            ParseError::Missing("start tile 'S'")
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::str::FromStr;
+
+use aoc_common::grid::Grid;
+use aoc_common::parsing::ParseError;
+
+/// Galaxies per row and per column. Distances are Manhattan, so the two
+/// axes add up independently and the galaxies' pairing along one axis
+/// doesn't matter.
+#[derive(Debug)]
+pub(crate) struct Image {
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+impl FromStr for Image {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_only(s, ".#")?;
+        let mut rows = vec![0; grid.height()];
+        let mut columns = vec![0; grid.width()];
+
+        for (pos, _) in grid.iter().filter(|&(_, &cell)| cell == b'#') {
+            rows[pos.y] += 1;
+            columns[pos.x] += 1;
+        }
+
+        Ok(Image { rows, columns })
+    }
+}
+
+/// Sum of the distances between every pair of galaxies along one axis,
+/// with every empty line counting as `factor` lines. Lines are visited in
+/// order, so each galaxy adds its distance to all the earlier ones from a
+/// running count and prefix sum of their positions.
+fn axis_distances(counts: &[u64], factor: u64) -> u64 {
+    let mut position = 0;
+    let mut seen = 0;
+    let mut prefix = 0;
+    let mut total = 0;
+
+    for &count in counts {
+        if count == 0 {
+            position += factor;
+            continue;
+        }
+        total += count * (seen * position - prefix);
+        seen += count;
+        prefix += count * position;
+        position += 1;
+    }
+
+    total
+}
+
+pub fn parse(input: &str) -> Result<Image, ParseError> {
+    Image::from_str(input)
+}
+
+pub fn total_distance(input: &str, factor: u64) -> u64 {
+    let image = parse(input).unwrap();
+
+    axis_distances(&image.rows, factor) + axis_distances(&image.columns, factor)
+}
+
+#[aoc(day11, part1)]
+pub fn part_1(input: &str) -> u64 {
+    total_distance(input, 2)
+}
+
+#[aoc(day11, part2)]
+pub fn part_2(input: &str) -> u64 {
+    total_distance(input, 1_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = r#"...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#....."#;
+
+    /// Every pair, on explicitly expanded coordinates
+    fn brute_force(input: &str, factor: u64) -> u64 {
+        let grid = Grid::parse(input).unwrap();
+        let empty_rows: Vec<bool> = grid.rows().map(|row| !row.contains(&b'#')).collect();
+        let empty_columns: Vec<bool> = (0..grid.width())
+            .map(|x| grid.rows().all(|row| row[x] != b'#'))
+            .collect();
+        let expand = |empty: &[bool], at: usize| {
+            empty[..at]
+                .iter()
+                .map(|&empty| if empty { factor } else { 1 })
+                .sum::<u64>()
+        };
+
+        let galaxies: Vec<(u64, u64)> = grid
+            .iter()
+            .filter(|(_, &cell)| cell == b'#')
+            .map(|(pos, _)| (expand(&empty_columns, pos.x), expand(&empty_rows, pos.y)))
+            .collect();
+        let mut total = 0;
+        for (i, a) in galaxies.iter().enumerate() {
+            for b in &galaxies[i + 1..] {
+                total += a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+            }
+        }
+        total
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE), 374);
+    }
+
+    #[test]
+    fn test_expansion_factors() {
+        assert_eq!(total_distance(SAMPLE, 10), 1030);
+        assert_eq!(total_distance(SAMPLE, 100), 8410);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        for factor in [1, 2, 10, 1_000_000] {
+            assert_eq!(total_distance(SAMPLE, factor), brute_force(SAMPLE, factor));
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use aoc_common::parsing::{only, parse_number, ParseError};
+use aoc_common::streaming::sum_lines;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Row {
+    springs: Vec<u8>,
+    groups: Vec<usize>,
+}
+
+impl TryFrom<&str> for Row {
+    type Error = ParseError;
+
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let (springs, groups) = line
+            .split_once(' ')
+            .ok_or(ParseError::Missing("' ' between springs and groups"))?;
+
+        let springs = only(springs, ".#?")?;
+        let groups = groups
+            .split(',')
+            .map(parse_number)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Row {
+            springs: springs.as_bytes().to_vec(),
+            groups,
+        })
+    }
+}
+
+impl Row {
+    /// The record repeated `factor` times, springs joined by `?`
+    pub fn unfold(&self, factor: usize) -> Row {
+        let mut springs = Vec::with_capacity((self.springs.len() + 1) * factor);
+        for copy in 0..factor {
+            if copy > 0 {
+                springs.push(b'?');
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+
+        Row {
+            springs,
+            groups: self.groups.repeat(factor),
+        }
+    }
+
+    /// Number of ways to fill in the `?`s so that the damaged runs match
+    /// the groups. `ways[i][g]` counts the arrangements of `springs[i..]`
+    /// holding exactly `groups[g..]`, filled in from the end.
+    pub fn arrangements(&self) -> u64 {
+        let (springs, groups) = (&self.springs, &self.groups);
+        let (n, g_count) = (springs.len(), groups.len());
+
+        // Operational springs before each position, to check a run in O(1)
+        let mut operational = vec![0; n + 1];
+        for (i, &spring) in springs.iter().enumerate() {
+            operational[i + 1] = operational[i] + (spring == b'.') as usize;
+        }
+
+        let stride = g_count + 1;
+        let mut ways = vec![0u64; (n + 1) * stride];
+        ways[n * stride + g_count] = 1;
+
+        for i in (0..n).rev() {
+            for g in 0..=g_count {
+                let mut count = 0;
+                if springs[i] != b'#' {
+                    count += ways[(i + 1) * stride + g];
+                }
+                if springs[i] != b'.' && g < g_count {
+                    let end = i + groups[g];
+                    let fits = end <= n
+                        && operational[end] == operational[i]
+                        && (end == n || springs[end] != b'#');
+                    if fits {
+                        // The spring after the run, if any, is operational
+                        count += ways[(end + 1).min(n) * stride + g + 1];
+                    }
+                }
+                ways[i * stride + g] = count;
+            }
+        }
+
+        ways[0]
+    }
+}
+
+pub fn total_arrangements(input: &str, unfold: usize) -> u64 {
+    sum_lines(input.as_bytes(), |line| {
+        Ok(Row::try_from(line)?.unfold(unfold).arrangements())
+    })
+    .unwrap()
+}
+
+#[aoc(day12, part1)]
+pub fn part_1(input: &str) -> u64 {
+    total_arrangements(input, 1)
+}
+
+#[aoc(day12, part2)]
+pub fn part_2(input: &str) -> u64 {
+    total_arrangements(input, 5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators;
+
+    static SAMPLE: &str = r#"???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1"#;
+
+    /// Tries every assignment of the `?`s
+    fn brute_force(row: &Row) -> u64 {
+        let unknown: Vec<usize> = (0..row.springs.len())
+            .filter(|&i| row.springs[i] == b'?')
+            .collect();
+
+        (0u32..1 << unknown.len())
+            .filter(|assignment| {
+                let mut springs = row.springs.clone();
+                for (bit, &i) in unknown.iter().enumerate() {
+                    springs[i] = if assignment >> bit & 1 == 1 {
+                        b'#'
+                    } else {
+                        b'.'
+                    };
+                }
+                let groups: Vec<usize> = springs
+                    .split(|&spring| spring == b'.')
+                    .filter(|run| !run.is_empty())
+                    .map(<[u8]>::len)
+                    .collect();
+                groups == row.groups
+            })
+            .count() as u64
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE), 21);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(SAMPLE), 525152);
+    }
+
+    #[test]
+    fn test_arrangements_per_row() {
+        let expected = [1, 4, 1, 1, 4, 10];
+        for (line, expected) in SAMPLE.lines().zip(expected) {
+            assert_eq!(Row::try_from(line).unwrap().arrangements(), expected);
+        }
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let input = generators::spring_rows(12, 300, 14);
+        for line in input.lines() {
+            let row = Row::try_from(line).unwrap();
+            let arrangements = row.arrangements();
+            assert!(arrangements >= 1, "{line}");
+            assert_eq!(arrangements, brute_force(&row), "{line}");
+            if row.springs.len() <= 7 {
+                assert_eq!(row.unfold(2).arrangements(), brute_force(&row.unfold(2)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_row_is_an_error() {
+        assert_eq!(
+            Row::try_from("#?. 1,,2").unwrap_err(),
+            ParseError::InvalidNumber(String::new())
+        );
+        assert_eq!(
+            Row::try_from("#?. 1 2").unwrap_err(),
+            ParseError::InvalidNumber("1 2".to_string())
+        );
+        assert_eq!(
+            Row::try_from("#?.#").unwrap_err(),
+            ParseError::Missing("' ' between springs and groups")
+        );
+    }
+}
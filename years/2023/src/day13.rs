@@ -0,0 +1,222 @@
+use std::{fmt, str::FromStr};
+
+use aoc_common::grid::Grid;
+use aoc_common::parsing::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// A vertical line, mirroring columns
+    Vertical,
+    /// A horizontal line, mirroring rows
+    Horizontal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reflection {
+    pub axis: Axis,
+    /// Columns left of a vertical line, or rows above a horizontal one
+    pub before: usize,
+}
+
+impl Reflection {
+    pub fn summary(&self) -> usize {
+        match self.axis {
+            Axis::Vertical => self.before,
+            Axis::Horizontal => 100 * self.before,
+        }
+    }
+}
+
+impl fmt::Display for Reflection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.axis {
+            Axis::Vertical => write!(
+                f,
+                "vertical line between columns {} and {}",
+                self.before,
+                self.before + 1
+            ),
+            Axis::Horizontal => write!(
+                f,
+                "horizontal line between rows {} and {}",
+                self.before,
+                self.before + 1
+            ),
+        }
+    }
+}
+
+/// Rows and columns as bitmasks, bit `i` set for a `#` at index `i`
+#[derive(Debug)]
+pub(crate) struct Pattern {
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+impl FromStr for Pattern {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_only(s, ".#")?;
+        if grid.width() > 64 || grid.height() > 64 {
+            return Err(ParseError::Overflow("pattern size"));
+        }
+
+        let mut rows = vec![0; grid.height()];
+        let mut columns = vec![0; grid.width()];
+        for (pos, _) in grid.iter().filter(|&(_, &cell)| cell == b'#') {
+            rows[pos.y] |= 1 << pos.x;
+            columns[pos.x] |= 1 << pos.y;
+        }
+
+        Ok(Pattern { rows, columns })
+    }
+}
+
+/// The first line between two of `lines` where the mirrored pairs differ
+/// in exactly `smudges` cells in total
+fn mirror(lines: &[u64], smudges: u32) -> Option<usize> {
+    (1..lines.len()).find(|&before| {
+        let reach = before.min(lines.len() - before);
+        let differences: u32 = (0..reach)
+            .map(|i| (lines[before - 1 - i] ^ lines[before + i]).count_ones())
+            .sum();
+        differences == smudges
+    })
+}
+
+impl Pattern {
+    /// Vertical lines are tried first, then horizontal ones
+    pub fn reflection(&self, smudges: u32) -> Option<Reflection> {
+        mirror(&self.columns, smudges)
+            .map(|before| Reflection {
+                axis: Axis::Vertical,
+                before,
+            })
+            .or_else(|| {
+                mirror(&self.rows, smudges).map(|before| Reflection {
+                    axis: Axis::Horizontal,
+                    before,
+                })
+            })
+    }
+}
+
+/// The reflection of every pattern, with exactly `smudges` cells fixed
+pub fn reflections(input: &str, smudges: u32) -> Vec<Option<Reflection>> {
+    input
+        .split("\n\n")
+        .map(|pattern| Pattern::from_str(pattern).unwrap().reflection(smudges))
+        .collect()
+}
+
+/// Which line and axis was chosen for each pattern, one pattern per line
+pub fn report(input: &str, smudges: u32) -> String {
+    reflections(input, smudges)
+        .iter()
+        .enumerate()
+        .map(|(i, reflection)| match reflection {
+            Some(reflection) => format!("pattern {}: {reflection}\n", i + 1),
+            None => format!("pattern {}: no reflection\n", i + 1),
+        })
+        .collect()
+}
+
+/// Patterns without a reflection add nothing
+pub fn summarize(input: &str, smudges: u32) -> usize {
+    reflections(input, smudges)
+        .iter()
+        .flatten()
+        .map(Reflection::summary)
+        .sum()
+}
+
+#[aoc(day13, part1)]
+pub fn part_1(input: &str) -> usize {
+    summarize(input, 0)
+}
+
+#[aoc(day13, part2)]
+pub fn part_2(input: &str) -> usize {
+    summarize(input, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = r#"#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#"#;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE), 405);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(SAMPLE), 400);
+    }
+
+    #[test]
+    fn test_reflections() {
+        assert_eq!(
+            reflections(SAMPLE, 0),
+            vec![
+                Some(Reflection {
+                    axis: Axis::Vertical,
+                    before: 5
+                }),
+                Some(Reflection {
+                    axis: Axis::Horizontal,
+                    before: 4
+                }),
+            ]
+        );
+        assert_eq!(
+            report(SAMPLE, 1),
+            "pattern 1: horizontal line between rows 3 and 4\n\
+             pattern 2: horizontal line between rows 1 and 2\n"
+        );
+    }
+
+    #[test]
+    fn test_any_number_of_smudges() {
+        // The rows mirror cleanly, the two columns differ in two cells
+        let pattern = Pattern::from_str("#.\n#.").unwrap();
+        let found = |smudges| pattern.reflection(smudges).map(|r| (r.axis, r.before));
+        assert_eq!(found(0), Some((Axis::Horizontal, 1)));
+        assert_eq!(found(1), None);
+        assert_eq!(found(2), Some((Axis::Vertical, 1)));
+        assert_eq!(report("#.\n#.", 3), "pattern 1: no reflection\n");
+    }
+
+    #[test]
+    fn test_pattern_wider_than_a_mask_is_an_error() {
+        let row = "#.".repeat(32);
+        assert!(Pattern::from_str(&format!("{row}\n{row}")).is_ok());
+        let row = row + "#";
+        assert_eq!(
+            Pattern::from_str(&format!("{row}\n{row}")).unwrap_err(),
+            ParseError::Overflow("pattern size")
+        );
+        let column = vec!["#"; 65].join("\n");
+        assert_eq!(
+            Pattern::from_str(&column).unwrap_err(),
+            ParseError::Overflow("pattern size")
+        );
+    }
+}
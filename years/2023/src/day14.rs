@@ -0,0 +1,184 @@
+use std::{fmt, str::FromStr};
+
+use aoc_common::grid::{Direction, Grid, Pos};
+use aoc_common::helpers::nth_state;
+use aoc_common::parsing::ParseError;
+
+/// Round rocks `O`, cube rocks `#` and empty space `.`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Platform {
+    grid: Grid<u8>,
+}
+
+impl FromStr for Platform {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Platform {
+            grid: Grid::parse_only(s, "O#.")?,
+        })
+    }
+}
+
+impl Platform {
+    /// The `i`th cell of a lane, counted from the edge rocks roll towards
+    fn lane_pos(&self, direction: Direction, lane: usize, i: usize) -> Pos {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        match direction {
+            Direction::Up => Pos::new(lane, i),
+            Direction::Down => Pos::new(lane, height - 1 - i),
+            Direction::Left => Pos::new(i, lane),
+            Direction::Right => Pos::new(width - 1 - i, lane),
+        }
+    }
+
+    /// Rolls every round rock as far as it goes towards `direction`
+    pub fn tilt(&mut self, direction: Direction) {
+        let (lanes, length) = match direction {
+            Direction::Up | Direction::Down => (self.grid.width(), self.grid.height()),
+            Direction::Left | Direction::Right => (self.grid.height(), self.grid.width()),
+        };
+
+        for lane in 0..lanes {
+            // Where the next round rock in this lane comes to rest
+            let mut free = 0;
+            for i in 0..length {
+                let pos = self.lane_pos(direction, lane, i);
+                match self.grid[pos] {
+                    b'#' => free = i + 1,
+                    b'O' => {
+                        self.grid[pos] = b'.';
+                        let rest = self.lane_pos(direction, lane, free);
+                        self.grid[rest] = b'O';
+                        free += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Tilts north, west, south, then east
+    pub fn spin_cycle(&mut self) {
+        for direction in [
+            Direction::Up,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+        ] {
+            self.tilt(direction);
+        }
+    }
+
+    /// Each round rock counts its distance from the south edge, inclusive
+    pub fn load(&self) -> usize {
+        self.grid
+            .iter()
+            .filter(|(_, &cell)| cell == b'O')
+            .map(|(pos, _)| self.grid.height() - pos.y)
+            .sum()
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.grid.fmt(f)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Platform, ParseError> {
+    Platform::from_str(input)
+}
+
+/// The north load after `cycles` spin cycles, skipping ahead once the
+/// platform comes back to an earlier arrangement
+pub fn load_after(input: &str, cycles: usize) -> usize {
+    let platform = parse(input).unwrap();
+
+    nth_state(platform, cycles, |platform| {
+        let mut next = platform.clone();
+        next.spin_cycle();
+        next
+    })
+    .load()
+}
+
+#[aoc(day14, part1)]
+pub fn part_1(input: &str) -> usize {
+    let mut platform = parse(input).unwrap();
+    platform.tilt(Direction::Up);
+
+    platform.load()
+}
+
+#[aoc(day14, part2)]
+pub fn part_2(input: &str) -> usize {
+    load_after(input, 1_000_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = r#"O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#...."#;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE), 136);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(SAMPLE), 64);
+    }
+
+    #[test]
+    fn test_spin_cycle() {
+        let mut platform = Platform::from_str(SAMPLE).unwrap();
+        platform.spin_cycle();
+        assert_eq!(
+            platform.to_string(),
+            ".....#....\n\
+             ....#...O#\n\
+             ...OO##...\n\
+             .OO#......\n\
+             .....OOO#.\n\
+             .O#...O#.#\n\
+             ....O#....\n\
+             ......OOOO\n\
+             #...O###..\n\
+             #..OO#....\n"
+        );
+    }
+
+    #[test]
+    fn test_tilt_each_direction() {
+        let tilted = |direction| {
+            let mut platform = Platform::from_str("O.#O.\n.O..O").unwrap();
+            platform.tilt(direction);
+            platform.to_string()
+        };
+        assert_eq!(tilted(Direction::Up), "OO#OO\n.....\n");
+        assert_eq!(tilted(Direction::Down), "..#..\nOO.OO\n");
+        assert_eq!(tilted(Direction::Left), "O.#O.\nOO...\n");
+        assert_eq!(tilted(Direction::Right), ".O#.O\n...OO\n");
+    }
+
+    #[test]
+    fn test_skipping_cycles_matches_simulation() {
+        let mut platform = Platform::from_str(SAMPLE).unwrap();
+        for cycles in 0..30 {
+            assert_eq!(load_after(SAMPLE, cycles), platform.load());
+            platform.spin_cycle();
+        }
+    }
+}
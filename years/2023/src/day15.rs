@@ -0,0 +1,225 @@
+use std::fmt;
+
+use aoc_common::parsing::{parse_number, ParseError};
+
+/// The Holiday ASCII String Helper: a running `(value + byte) * 17 % 256`
+pub fn hash(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |value, &byte| {
+        value.wrapping_add(byte).wrapping_mul(17)
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// `label-`: take the lens out of its box
+    Remove,
+    /// `label=focal`: replace the lens in place, or add it at the back
+    Insert(u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step<'a> {
+    pub label: &'a str,
+    pub operation: Operation,
+}
+
+impl<'a> TryFrom<&'a str> for Step<'a> {
+    type Error = ParseError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        if let Some(label) = s.strip_suffix('-') {
+            return Ok(Step {
+                label,
+                operation: Operation::Remove,
+            });
+        }
+        let (label, focal) = s
+            .split_once('=')
+            .ok_or(ParseError::Missing("'=' or '-' after the label"))?;
+
+        Ok(Step {
+            label,
+            operation: Operation::Insert(parse_number(focal)?),
+        })
+    }
+}
+
+impl Step<'_> {
+    pub fn box_index(&self) -> usize {
+        hash(self.label.as_bytes()) as usize
+    }
+}
+
+impl fmt::Display for Step<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.operation {
+            Operation::Remove => write!(f, "{}-", self.label),
+            Operation::Insert(focal) => write!(f, "{}={focal}", self.label),
+        }
+    }
+}
+
+/// The 256 boxes, each holding its lenses front to back
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Boxes<'a> {
+    boxes: Vec<Vec<(&'a str, u8)>>,
+}
+
+impl Default for Boxes<'_> {
+    fn default() -> Self {
+        Boxes {
+            boxes: vec![Vec::new(); 256],
+        }
+    }
+}
+
+impl<'a> Boxes<'a> {
+    pub fn apply(&mut self, step: &Step<'a>) {
+        let lenses = &mut self.boxes[step.box_index()];
+        let slot = lenses.iter().position(|&(label, _)| label == step.label);
+
+        match (step.operation, slot) {
+            (Operation::Remove, Some(slot)) => {
+                lenses.remove(slot);
+            }
+            (Operation::Remove, None) => {}
+            (Operation::Insert(focal), Some(slot)) => lenses[slot].1 = focal,
+            (Operation::Insert(focal), None) => lenses.push((step.label, focal)),
+        }
+    }
+
+    /// The lenses of box `index`, front to back
+    pub fn lenses(&self, index: u8) -> &[(&'a str, u8)] {
+        &self.boxes[index as usize]
+    }
+
+    /// Sum over every lens of its box number plus one, its slot (from one)
+    /// and its focal length
+    pub fn focusing_power(&self) -> usize {
+        self.boxes
+            .iter()
+            .enumerate()
+            .flat_map(|(index, lenses)| {
+                lenses
+                    .iter()
+                    .enumerate()
+                    .map(move |(slot, &(_, focal))| (index + 1) * (slot + 1) * focal as usize)
+            })
+            .sum()
+    }
+}
+
+/// The non-empty boxes in the puzzle's notation, `Box 3: [ot 7] [ab 5]`
+impl fmt::Display for Boxes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, lenses) in self.boxes.iter().enumerate() {
+            if lenses.is_empty() {
+                continue;
+            }
+            write!(f, "Box {index}:")?;
+            for (label, focal) in lenses {
+                write!(f, " [{label} {focal}]")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// The comma separated steps, ignoring line breaks
+fn steps(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split(',')
+        .map(|step| step.trim_matches(|c| c == '\n' || c == '\r'))
+}
+
+/// Every box's lenses after each step, for following an instruction stream
+pub fn trace(input: &str) -> String {
+    let mut boxes = Boxes::default();
+    let mut out = String::new();
+
+    for step in steps(input) {
+        let step = Step::try_from(step).unwrap();
+        boxes.apply(&step);
+        out += &format!("After \"{step}\":\n{boxes}\n");
+    }
+
+    out
+}
+
+#[aoc(day15, part1)]
+pub fn part_1(input: &str) -> usize {
+    steps(input)
+        .map(|step| hash(step.as_bytes()) as usize)
+        .sum()
+}
+
+#[aoc(day15, part2)]
+pub fn part_2(input: &str) -> usize {
+    let mut boxes = Boxes::default();
+    for step in steps(input) {
+        boxes.apply(&Step::try_from(step).unwrap());
+    }
+
+    boxes.focusing_power()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = r#"rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
+"#;
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(b"HASH"), 52);
+        assert_eq!(hash(b"rn"), 0);
+        assert_eq!(hash(b"qp"), 1);
+        assert_eq!(hash(b""), 0);
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE), 1320);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(SAMPLE), 145);
+    }
+
+    #[test]
+    fn test_trace() {
+        let trace = trace(SAMPLE);
+        assert!(trace.starts_with("After \"rn=1\":\nBox 0: [rn 1]\n\nAfter \"cm-\":\n"));
+        assert!(trace.ends_with(
+            "After \"ot=7\":\n\
+             Box 0: [rn 1] [cm 2]\n\
+             Box 3: [ot 7] [ab 5] [pc 6]\n\n"
+        ));
+    }
+
+    #[test]
+    fn test_boxes() {
+        let mut boxes = Boxes::default();
+        for step in steps(SAMPLE) {
+            boxes.apply(&Step::try_from(step).unwrap());
+        }
+        assert_eq!(boxes.lenses(0), &[("rn", 1), ("cm", 2)]);
+        assert_eq!(boxes.lenses(1), &[]);
+        assert_eq!(boxes.lenses(3), &[("ot", 7), ("ab", 5), ("pc", 6)]);
+    }
+
+    #[test]
+    fn test_invalid_step_is_an_error() {
+        assert_eq!(
+            Step::try_from("rn").unwrap_err(),
+            ParseError::Missing("'=' or '-' after the label")
+        );
+        assert_eq!(
+            Step::try_from("rn=x").unwrap_err(),
+            ParseError::InvalidNumber("x".to_string())
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::str::FromStr;
+
+use aoc_common::execution::map_reduce;
+use aoc_common::grid::{Direction, Grid, Pos};
+use aoc_common::parsing::ParseError;
+
+/// Where a beam is and which way it is heading
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Beam {
+    pub pos: Pos,
+    pub heading: Direction,
+}
+
+/// Mirrors `/` and `\`, splitters `|` and `-`, empty space `.`
+#[derive(Debug)]
+pub(crate) struct Contraption {
+    tiles: Grid<u8>,
+}
+
+impl FromStr for Contraption {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = Grid::parse_only(s, "./\\|-")?;
+        // A blank line parses as a grid without tiles, with nowhere to enter
+        if tiles.width() == 0 {
+            return Err(ParseError::Missing("grid rows"));
+        }
+
+        Ok(Contraption { tiles })
+    }
+}
+
+/// The headings a beam leaves `tile` with, the second one for a split
+fn deflect(tile: u8, heading: Direction) -> (Direction, Option<Direction>) {
+    use Direction::*;
+
+    match (tile, heading) {
+        (b'/', Right) | (b'\\', Left) => (Up, None),
+        (b'/', Left) | (b'\\', Right) => (Down, None),
+        (b'/', Up) | (b'\\', Down) => (Right, None),
+        (b'/', Down) | (b'\\', Up) => (Left, None),
+        (b'|', Left | Right) => (Up, Some(Down)),
+        (b'-', Up | Down) => (Left, Some(Right)),
+        _ => (heading, None),
+    }
+}
+
+impl Contraption {
+    /// Every beam edge entry: down from the top row, up from the bottom
+    /// one, right from the left column and left from the right one
+    pub fn entries(&self) -> Vec<Beam> {
+        let (width, height) = (self.tiles.width(), self.tiles.height());
+        let beam = |x, y, heading| Beam {
+            pos: Pos::new(x, y),
+            heading,
+        };
+
+        (0..width)
+            .flat_map(|x| {
+                [
+                    beam(x, 0, Direction::Down),
+                    beam(x, height - 1, Direction::Up),
+                ]
+            })
+            .chain((0..height).flat_map(|y| {
+                [
+                    beam(0, y, Direction::Right),
+                    beam(width - 1, y, Direction::Left),
+                ]
+            }))
+            .collect()
+    }
+
+    /// The tiles a beam entering at `start` passes through. Each tile keeps
+    /// a bitmask of the headings seen on it, so every beam state is
+    /// followed once and loops end.
+    pub fn energize(&self, start: Beam) -> Grid<bool> {
+        let mut seen = Grid::new(self.tiles.width(), self.tiles.height(), 0u8);
+        let mut beams = vec![start];
+
+        while let Some(Beam { pos, heading }) = beams.pop() {
+            let bit = 1 << heading.index();
+            if seen[pos] & bit != 0 {
+                continue;
+            }
+            seen[pos] |= bit;
+
+            let (first, second) = deflect(self.tiles[pos], heading);
+            for heading in std::iter::once(first).chain(second) {
+                if let Some(pos) = self.tiles.step(pos, heading) {
+                    beams.push(Beam { pos, heading });
+                }
+            }
+        }
+
+        seen.map(|_, &headings| headings != 0)
+    }
+
+    pub fn energized(&self, start: Beam) -> usize {
+        self.energize(start)
+            .iter()
+            .filter(|(_, &energized)| energized)
+            .count()
+    }
+
+    /// The entry energizing the most tiles, ties going to the largest
+    /// entry so the answer doesn't depend on the thread schedule
+    pub fn best_entry(&self) -> Option<(usize, Beam)> {
+        map_reduce(
+            self.entries(),
+            |entry| Some((self.energized(entry), entry)),
+            || None,
+            Ord::max,
+        )
+    }
+}
+
+const TOP_LEFT: Beam = Beam {
+    pos: Pos::new(0, 0),
+    heading: Direction::Right,
+};
+
+pub fn parse(input: &str) -> Result<Contraption, ParseError> {
+    Contraption::from_str(input)
+}
+
+/// Energized tiles as `#`, the rest as `.`, for a beam from the top left
+pub fn render(input: &str) -> String {
+    let contraption = parse(input).unwrap();
+
+    contraption
+        .energize(TOP_LEFT)
+        .render(|_, &energized| if energized { '#' } else { '.' })
+}
+
+#[aoc(day16, part1)]
+pub fn part_1(input: &str) -> usize {
+    parse(input).unwrap().energized(TOP_LEFT)
+}
+
+#[aoc(day16, part2)]
+pub fn part_2(input: &str) -> usize {
+    parse(input)
+        .unwrap()
+        .best_entry()
+        .expect("a contraption with tiles")
+        .0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::execution::Execution;
+
+    static SAMPLE: &str = r#".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|...."#;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE), 46);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(SAMPLE), 51);
+    }
+
+    #[test]
+    fn test_best_entry() {
+        let contraption = Contraption::from_str(SAMPLE).unwrap();
+        let best = Beam {
+            pos: Pos::new(3, 0),
+            heading: Direction::Down,
+        };
+        for execution in [Execution::Parallel, Execution::Sequential] {
+            assert_eq!(
+                execution.install(|| contraption.best_entry()),
+                Some((51, best))
+            );
+        }
+        assert_eq!(contraption.entries().len(), 40);
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            render(SAMPLE),
+            "######....\n\
+             .#...#....\n\
+             .#...#####\n\
+             .#...##...\n\
+             .#...##...\n\
+             .#...##...\n\
+             .#..####..\n\
+             ########..\n\
+             .#######..\n\
+             .#...#.#..\n"
+        );
+    }
+
+    #[test]
+    fn test_loops_end() {
+        // The beam circles the four mirrors forever
+        assert_eq!(part_1("-.\\\n...\n\\./"), 8);
+    }
+
+    #[test]
+    fn test_blank_contraption_is_an_error() {
+        for blank in ["", "\n"] {
+            assert_eq!(
+                Contraption::from_str(blank).unwrap_err(),
+                ParseError::Missing("grid rows")
+            );
+        }
+    }
+}
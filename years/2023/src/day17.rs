@@ -0,0 +1,169 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use aoc_common::grid::{Direction, Grid, Pos};
+use aoc_common::parsing::ParseError;
+use aoc_common::search::dijkstra;
+
+/// A crucible on a block, with how many blocks it has just moved straight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct State {
+    pos: Pos,
+    heading: Direction,
+    run: usize,
+}
+
+/// The heat loss of entering each city block
+#[derive(Debug)]
+pub(crate) struct City {
+    heat_loss: Grid<u8>,
+}
+
+impl FromStr for City {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_only(s, "0123456789")?;
+
+        Ok(City {
+            heat_loss: grid.map(|_, &digit| digit - b'0'),
+        })
+    }
+}
+
+/// The least heat loss from the top left to the bottom right block and the
+/// blocks along the way, both ends included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub heat_loss: u64,
+    pub path: Vec<Pos>,
+}
+
+impl City {
+    /// The best route for a crucible that moves `runs.start()` to
+    /// `runs.end()` blocks in a line before it turns, and has to have moved
+    /// at least `runs.start()` to stop at the end
+    pub fn route(&self, runs: RangeInclusive<usize>) -> Option<Route> {
+        let (min_run, max_run) = (*runs.start(), *runs.end());
+        let end = Pos::new(self.heat_loss.width() - 1, self.heat_loss.height() - 1);
+        let starts = [Direction::Right, Direction::Down].map(|heading| State {
+            pos: Pos::new(0, 0),
+            heading,
+            run: 0,
+        });
+
+        let neighbours = |&state: &State| {
+            let mut next = Vec::with_capacity(3);
+            if state.run < max_run {
+                next.push((state.heading, state.run + 1));
+            }
+            // Turning needs a run behind it, even when the minimum is zero
+            if state.run >= min_run.max(1) {
+                next.push((state.heading.turn_left(), 1));
+                next.push((state.heading.turn_right(), 1));
+            }
+
+            next.into_iter().filter_map(move |(heading, run)| {
+                let pos = self.heat_loss.step(state.pos, heading)?;
+                Some((State { pos, heading, run }, self.heat_loss[pos] as u64))
+            })
+        };
+
+        let path = dijkstra(starts, neighbours, |state| {
+            state.pos == end && state.run >= min_run
+        })?;
+
+        Some(Route {
+            heat_loss: path.cost,
+            path: path.states.iter().map(|state| state.pos).collect(),
+        })
+    }
+}
+
+pub fn parse(input: &str) -> Result<City, ParseError> {
+    City::from_str(input)
+}
+
+pub fn least_heat_loss(input: &str, runs: RangeInclusive<usize>) -> u64 {
+    parse(input)
+        .unwrap()
+        .route(runs)
+        .expect("no route to the factory")
+        .heat_loss
+}
+
+#[aoc(day17, part1)]
+pub fn part_1(input: &str) -> u64 {
+    least_heat_loss(input, 0..=3)
+}
+
+#[aoc(day17, part2)]
+pub fn part_2(input: &str) -> u64 {
+    least_heat_loss(input, 4..=10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = r#"2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533"#;
+
+    static SAMPLE_2: &str = r#"111111111111
+999999999991
+999999999991
+999999999991
+999999999991"#;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE), 102);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(SAMPLE), 94);
+        assert_eq!(part_2(SAMPLE_2), 71);
+    }
+
+    #[test]
+    fn test_route() {
+        let city = City::from_str(SAMPLE).unwrap();
+        let route = city.route(0..=3).unwrap();
+        assert_eq!(route.path.first(), Some(&Pos::new(0, 0)));
+        assert_eq!(route.path.last(), Some(&Pos::new(12, 12)));
+
+        // The path moves one block at a time and adds up to its heat loss
+        for pair in route.path.windows(2) {
+            assert_eq!(
+                pair[0].x.abs_diff(pair[1].x) + pair[0].y.abs_diff(pair[1].y),
+                1
+            );
+        }
+        let heat_loss: u64 = route.path[1..]
+            .iter()
+            .map(|&pos| city.heat_loss[pos] as u64)
+            .sum();
+        assert_eq!(heat_loss, route.heat_loss);
+    }
+
+    #[test]
+    fn test_run_limits() {
+        // Straight along the top is cheapest but too long a run
+        let city = City::from_str("1111\n9991").unwrap();
+        assert_eq!(city.route(0..=4).unwrap().heat_loss, 4);
+        assert_eq!(city.route(0..=2).unwrap().heat_loss, 12);
+        assert_eq!(city.route(2..=3), None);
+    }
+}
@@ -0,0 +1,167 @@
+use aoc_common::geometry::covered_points;
+use aoc_common::grid::Direction;
+use aoc_common::parsing::{parse_number, ParseError};
+
+/// Which half of a dig plan line holds the instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// `R 6`, the direction letter and the length
+    Plain,
+    /// `(#70c710)`, five hex digits of length then the direction 0-3
+    Hex,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub length: i64,
+}
+
+/// The first character of a field that isn't a direction
+fn invalid(direction: &str) -> ParseError {
+    ParseError::InvalidChar(direction.chars().next().unwrap())
+}
+
+impl Instruction {
+    pub fn parse(line: &str, encoding: Encoding) -> Result<Self, ParseError> {
+        let mut fields = line.split_whitespace();
+        let direction = fields.next().ok_or(ParseError::Missing("direction"))?;
+        let length = fields.next().ok_or(ParseError::Missing("length"))?;
+        let colour = fields.next().ok_or(ParseError::Missing("colour"))?;
+
+        match encoding {
+            Encoding::Plain => Ok(Instruction {
+                direction: match direction {
+                    "U" => Direction::Up,
+                    "R" => Direction::Right,
+                    "D" => Direction::Down,
+                    "L" => Direction::Left,
+                    _ => return Err(invalid(direction)),
+                },
+                length: parse_number(length)?,
+            }),
+            Encoding::Hex => {
+                let hex = colour
+                    .strip_prefix("(#")
+                    .and_then(|hex| hex.strip_suffix(')'))
+                    .filter(|hex| hex.len() == 6 && hex.is_ascii())
+                    .ok_or(ParseError::Missing("six digit colour '(#rrggbb)'"))?;
+                let (length, direction) = hex.split_at(5);
+
+                Ok(Instruction {
+                    direction: match direction {
+                        "0" => Direction::Right,
+                        "1" => Direction::Down,
+                        "2" => Direction::Left,
+                        "3" => Direction::Up,
+                        _ => return Err(invalid(direction)),
+                    },
+                    length: i64::from_str_radix(length, 16)
+                        .map_err(|_| ParseError::InvalidNumber(length.to_string()))?,
+                })
+            }
+        }
+    }
+}
+
+/// The corners of the trench, starting and ending at the origin
+fn corners(instructions: &[Instruction]) -> Vec<(i64, i64)> {
+    instructions
+        .iter()
+        .scan((0, 0), |(x, y), instruction| {
+            let (dx, dy) = instruction.direction.delta();
+            *x += dx * instruction.length;
+            *y += dy * instruction.length;
+            Some((*x, *y))
+        })
+        .collect()
+}
+
+/// Cubic metres of lava the trench and its dug out interior hold
+pub fn lagoon_size(input: &str, encoding: Encoding) -> i64 {
+    let instructions: Vec<Instruction> = input
+        .lines()
+        .map(|line| Instruction::parse(line, encoding).unwrap())
+        .collect();
+
+    covered_points(&corners(&instructions))
+}
+
+#[aoc(day18, part1)]
+pub fn part_1(input: &str) -> i64 {
+    lagoon_size(input, Encoding::Plain)
+}
+
+#[aoc(day18, part2)]
+pub fn part_2(input: &str) -> i64 {
+    lagoon_size(input, Encoding::Hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = r#"R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)"#;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE), 62);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(SAMPLE), 952408144115);
+    }
+
+    #[test]
+    fn test_encodings() {
+        let line = "R 6 (#70c710)";
+        assert_eq!(
+            Instruction::parse(line, Encoding::Plain),
+            Ok(Instruction {
+                direction: Direction::Right,
+                length: 6
+            })
+        );
+        assert_eq!(
+            Instruction::parse(line, Encoding::Hex),
+            Ok(Instruction {
+                direction: Direction::Right,
+                length: 461937
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_instruction_is_an_error() {
+        assert_eq!(
+            Instruction::parse("X 6 (#70c710)", Encoding::Plain),
+            Err(ParseError::InvalidChar('X'))
+        );
+        assert_eq!(
+            Instruction::parse("R 6 (#70c714)", Encoding::Hex),
+            Err(ParseError::InvalidChar('4'))
+        );
+        assert_eq!(
+            Instruction::parse("R 6 (#70c7)", Encoding::Hex),
+            Err(ParseError::Missing("six digit colour '(#rrggbb)'"))
+        );
+        assert_eq!(
+            Instruction::parse("R 6", Encoding::Plain),
+            Err(ParseError::Missing("colour"))
+        );
+    }
+}
@@ -0,0 +1,500 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::{fmt, str::FromStr};
+
+use aoc_common::intervals::{len, split};
+use aoc_common::parsing::{parse_number, ParseError};
+
+/// The four rating categories, in the order parts list them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+impl Category {
+    fn parse(s: &str) -> Result<Self, ParseError> {
+        match s {
+            "x" => Ok(Category::X),
+            "m" => Ok(Category::M),
+            "a" => Ok(Category::A),
+            "s" => Ok(Category::S),
+            _ => Err(ParseError::InvalidChar(s.chars().next().unwrap_or(' '))),
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(["x", "m", "a", "s"][self.index()])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+impl From<&str> for Target {
+    fn from(s: &str) -> Self {
+        match s {
+            "A" => Target::Accept,
+            "R" => Target::Reject,
+            name => Target::Workflow(name.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Accept => f.write_str("A"),
+            Target::Reject => f.write_str("R"),
+            Target::Workflow(name) => f.write_str(name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Condition {
+    Less(Category, u64),
+    Greater(Category, u64),
+}
+
+impl Condition {
+    fn matches(self, part: &Part) -> bool {
+        match self {
+            Condition::Less(category, value) => part.0[category.index()] < value,
+            Condition::Greater(category, value) => part.0[category.index()] > value,
+        }
+    }
+
+    /// The ratings of `cube` that match and the ones that don't
+    fn split(self, cube: &Cube) -> (Option<Cube>, Option<Cube>) {
+        let (category, below, above) = match self {
+            Condition::Less(category, value) => {
+                let (below, above) = split(&cube[category.index()], value);
+                (category, below, above)
+            }
+            Condition::Greater(category, value) => {
+                // No rating is above `u64::MAX`, and splitting there
+                // leaves every rating below
+                let (below, above) = split(&cube[category.index()], value.saturating_add(1));
+                (category, above, below)
+            }
+        };
+        let with = |range: Option<Range<u64>>| {
+            range.map(|range| {
+                let mut cube = cube.clone();
+                cube[category.index()] = range;
+                cube
+            })
+        };
+
+        (with(below), with(above))
+    }
+}
+
+/// `a<2006:qkq`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub condition: Condition,
+    pub target: Target,
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (condition, target) = s
+            .split_once(':')
+            .ok_or(ParseError::Missing("':' before the rule's target"))?;
+        let at = condition
+            .find(['<', '>'])
+            .ok_or(ParseError::Missing("'<' or '>' in the rule"))?;
+        let category = Category::parse(&condition[..at])?;
+        let value = parse_number(&condition[at + 1..])?;
+
+        Ok(Rule {
+            condition: match &condition[at..at + 1] {
+                "<" => Condition::Less(category, value),
+                _ => Condition::Greater(category, value),
+            },
+            target: target.into(),
+        })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.condition {
+            Condition::Less(category, value) => write!(f, "{category}<{value}")?,
+            Condition::Greater(category, value) => write!(f, "{category}>{value}")?,
+        }
+        write!(f, ":{}", self.target)
+    }
+}
+
+/// `px{a<2006:qkq,m>2090:A,rfg}`, the rules tried in order and the target
+/// for parts matching none of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workflow {
+    pub name: String,
+    pub rules: Vec<Rule>,
+    pub fallback: Target,
+}
+
+impl FromStr for Workflow {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, body) = s
+            .strip_suffix('}')
+            .and_then(|s| s.split_once('{'))
+            .ok_or(ParseError::Missing("'{...}' around the rules"))?;
+        let (rules, fallback) = body.rsplit_once(',').unwrap_or(("", body));
+
+        Ok(Workflow {
+            name: name.to_string(),
+            rules: rules
+                .split(',')
+                .filter(|rule| !rule.is_empty())
+                .map(str::parse)
+                .collect::<Result<_, _>>()?,
+            fallback: fallback.into(),
+        })
+    }
+}
+
+impl fmt::Display for Workflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{{", self.name)?;
+        for rule in &self.rules {
+            write!(f, "{rule},")?;
+        }
+        write!(f, "{}}}", self.fallback)
+    }
+}
+
+impl Workflow {
+    fn target(&self, part: &Part) -> &Target {
+        self.rules
+            .iter()
+            .find(|rule| rule.condition.matches(part))
+            .map_or(&self.fallback, |rule| &rule.target)
+    }
+}
+
+/// Ratings `{x=787,m=2655,a=1222,s=2876}`, indexed by [`Category`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Part([u64; 4]);
+
+impl FromStr for Part {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ratings = s
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .ok_or(ParseError::Missing("'{...}' around the ratings"))?;
+
+        let mut part = [0; 4];
+        for rating in ratings.split(',') {
+            let (category, value) = rating
+                .split_once('=')
+                .ok_or(ParseError::Missing("'=' in the rating"))?;
+            part[Category::parse(category)?.index()] = parse_number(value)?;
+        }
+
+        Ok(Part(part))
+    }
+}
+
+/// A range of ratings per category
+type Cube = [Range<u64>; 4];
+
+#[derive(Debug)]
+pub(crate) struct System {
+    workflows: HashMap<String, Workflow>,
+}
+
+impl FromStr for System {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let workflows: HashMap<String, Workflow> = s
+            .lines()
+            .map(|line| {
+                line.parse()
+                    .map(|workflow: Workflow| (workflow.name.clone(), workflow))
+            })
+            .collect::<Result<_, _>>()?;
+
+        if !workflows.contains_key("in") {
+            return Err(ParseError::UnknownNode("in".to_string()));
+        }
+        let targets = workflows.values().flat_map(|workflow| {
+            workflow
+                .rules
+                .iter()
+                .map(|rule| &rule.target)
+                .chain([&workflow.fallback])
+        });
+        for target in targets {
+            if let Target::Workflow(name) = target {
+                if !workflows.contains_key(name) {
+                    return Err(ParseError::UnknownNode(name.clone()));
+                }
+            }
+        }
+
+        let mut done = HashSet::new();
+        if let Some(name) = find_loop(&workflows, "in", &mut Vec::new(), &mut done) {
+            return Err(ParseError::Cycle(name.to_string()));
+        }
+
+        Ok(System { workflows })
+    }
+}
+
+/// A workflow that some path from `name` comes back to, depth first.
+/// `path` holds the workflows on the way to `name`, `done` the ones with
+/// nothing but dead ends after them.
+fn find_loop<'a>(
+    workflows: &'a HashMap<String, Workflow>,
+    name: &'a str,
+    path: &mut Vec<&'a str>,
+    done: &mut HashSet<&'a str>,
+) -> Option<&'a str> {
+    if path.contains(&name) {
+        return Some(name);
+    }
+    if done.contains(name) {
+        return None;
+    }
+
+    path.push(name);
+    let workflow = &workflows[name];
+    let targets = workflow
+        .rules
+        .iter()
+        .map(|rule| &rule.target)
+        .chain([&workflow.fallback]);
+    for target in targets {
+        if let Target::Workflow(next) = target {
+            if let Some(name) = find_loop(workflows, next, path, done) {
+                return Some(name);
+            }
+        }
+    }
+    path.pop();
+    done.insert(name);
+
+    None
+}
+
+impl System {
+    pub fn accepts(&self, part: &Part) -> bool {
+        let mut target = &Target::Workflow("in".to_string());
+        loop {
+            match target {
+                Target::Accept => return true,
+                Target::Reject => return false,
+                Target::Workflow(name) => target = self.workflows[name].target(part),
+            }
+        }
+    }
+
+    /// Rating combinations in `cube` that end up accepted from `target`.
+    /// Each rule splits the cube into the part it sends on and the part
+    /// left for the next rule.
+    fn accepted(&self, target: &Target, cube: Cube) -> u64 {
+        let workflow = match target {
+            Target::Accept => return cube.iter().map(len).product(),
+            Target::Reject => return 0,
+            Target::Workflow(name) => &self.workflows[name],
+        };
+
+        let mut total = 0;
+        let mut rest = Some(cube);
+        for rule in &workflow.rules {
+            let Some(cube) = rest else {
+                return total;
+            };
+            let (matched, unmatched) = rule.condition.split(&cube);
+            if let Some(matched) = matched {
+                total += self.accepted(&rule.target, matched);
+            }
+            rest = unmatched;
+        }
+
+        total + rest.map_or(0, |cube| self.accepted(&workflow.fallback, cube))
+    }
+
+    /// Combinations of ratings from 1 to 4000 that are accepted
+    pub fn combinations(&self) -> u64 {
+        let cube = [1..4001, 1..4001, 1..4001, 1..4001];
+        self.accepted(&Target::Workflow("in".to_string()), cube)
+    }
+}
+
+pub fn parse(input: &str) -> Result<(System, Vec<Part>), ParseError> {
+    let (workflows, parts) = input.split_once("\n\n").ok_or(ParseError::Missing(
+        "blank line between workflows and parts",
+    ))?;
+
+    Ok((
+        workflows.parse()?,
+        parts.lines().map(str::parse).collect::<Result<_, _>>()?,
+    ))
+}
+
+#[aoc(day19, part1)]
+pub fn part_1(input: &str) -> u64 {
+    let (system, parts) = parse(input).unwrap();
+
+    parts
+        .iter()
+        .filter(|part| system.accepts(part))
+        .map(|part| part.0.iter().sum::<u64>())
+        .sum()
+}
+
+#[aoc(day19, part2)]
+pub fn part_2(input: &str) -> u64 {
+    parse(input).unwrap().0.combinations()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = r#"px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}"#;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE), 19114);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(SAMPLE), 167409079868000);
+    }
+
+    #[test]
+    fn test_workflow_ast() {
+        let workflow: Workflow = "px{a<2006:qkq,m>2090:A,rfg}".parse().unwrap();
+        assert_eq!(
+            workflow,
+            Workflow {
+                name: "px".to_string(),
+                rules: vec![
+                    Rule {
+                        condition: Condition::Less(Category::A, 2006),
+                        target: Target::Workflow("qkq".to_string()),
+                    },
+                    Rule {
+                        condition: Condition::Greater(Category::M, 2090),
+                        target: Target::Accept,
+                    },
+                ],
+                fallback: Target::Workflow("rfg".to_string()),
+            }
+        );
+        assert_eq!(workflow.to_string(), "px{a<2006:qkq,m>2090:A,rfg}");
+    }
+
+    #[test]
+    fn test_counting_matches_evaluation() {
+        // Every part of a small cube, run through the workflows one by one
+        let (system, _) = parse(SAMPLE).unwrap();
+        let values = [1, 537, 838, 1351, 1416, 1548, 1716, 2662, 3448, 4000];
+        let cube = [1416..1417, 838..1549, 1716..1717, 1351..1352];
+        let mut accepted = 0;
+        for m in cube[1].clone() {
+            let part = Part([1416, m, 1716, 1351]);
+            accepted += system.accepts(&part) as u64;
+        }
+        let start = Target::Workflow("in".to_string());
+        assert_eq!(system.accepted(&start, cube), accepted);
+
+        for x in values {
+            for s in values {
+                let part = Part([x, 2000, 1000, s]);
+                let cube = [x..x + 1, 2000..2001, 1000..1001, s..s + 1];
+                assert_eq!(
+                    system.accepted(&start, cube),
+                    system.accepts(&part) as u64,
+                    "{part:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_greater_than_the_largest_rating() {
+        let system: System = format!("in{{x>{}:R,A}}", u64::MAX).parse().unwrap();
+        assert_eq!(system.combinations(), 4000 * 4000 * 4000 * 4000);
+    }
+
+    #[test]
+    fn test_invalid_workflows_are_errors() {
+        assert_eq!(
+            "in{a<5:nope,A}".parse::<System>().unwrap_err(),
+            ParseError::UnknownNode("nope".to_string())
+        );
+        assert_eq!(
+            "px{A}".parse::<System>().unwrap_err(),
+            ParseError::UnknownNode("in".to_string())
+        );
+        assert_eq!(
+            "a{x>1:b,R}\nb{m<3:in,A}\nin{a}"
+                .parse::<System>()
+                .unwrap_err(),
+            ParseError::Cycle("in".to_string())
+        );
+        // A loop that can't be reached from `in` is never followed
+        assert_eq!(
+            "in{a<5:px,A}\npx{s>2:qs,R}\nqs{A}\nloop{x<1:loop,A}"
+                .parse::<System>()
+                .map(|system| system.combinations()),
+            Ok(4000 * 4000 * 4000 * 4000 - 4 * 2 * 4000 * 4000)
+        );
+        assert_eq!(
+            "in{q<5:A,R}".parse::<System>().unwrap_err(),
+            ParseError::InvalidChar('q')
+        );
+        assert_eq!(
+            "{x=1,m=2,a=3}s=4".parse::<Part>().unwrap_err(),
+            ParseError::Missing("'{...}' around the ratings")
+        );
+        assert_eq!(
+            parse("in{A}").unwrap_err(),
+            ParseError::Missing("blank line between workflows and parts")
+        );
+    }
+}
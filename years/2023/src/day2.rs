@@ -1,4 +1,7 @@
-use rayon::prelude::*;
+use std::io::{self, BufRead};
+
+use aoc_common::parsing::{parse_number, ParseError};
+use aoc_common::streaming::sum_lines;
 
 #[derive(Debug, PartialEq)]
 enum Color {
@@ -56,16 +59,22 @@ struct AvailableCubes {
 }
 
 #[derive(Debug)]
-struct GameData {
+pub(crate) struct GameData {
     id: usize,
     cube_subsets: [Option<SubGame>; 5],
 }
 
-impl From<&str> for GameData {
-    fn from(line: &str) -> Self {
-        let colon_index = line.find(':').unwrap();
-        let id_str = &line[5..colon_index];
-        let id = id_str.trim().parse::<usize>().unwrap();
+impl TryFrom<&str> for GameData {
+    type Error = ParseError;
+
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let colon_index = line
+            .find(':')
+            .ok_or(ParseError::Missing("':' after game id"))?;
+        let id_str = line
+            .get(5..colon_index)
+            .ok_or(ParseError::Missing("game id"))?;
+        let id = parse_number(id_str.trim())?;
         let cube_data_str = &line[colon_index + 1..];
 
         let mut cube_subsets = [None, None, None, None, None];
@@ -76,7 +85,7 @@ impl From<&str> for GameData {
                 break;
             }
         }
-        GameData { id, cube_subsets }
+        Ok(GameData { id, cube_subsets })
     }
 }
 
@@ -140,35 +149,39 @@ impl GameData {
 
 #[aoc(day2, part1, FirstTry)]
 pub fn part1_first(input: &str) -> usize {
+    part1_stream(input.as_bytes()).unwrap()
+}
+
+pub fn part1_stream<R: BufRead>(reader: R) -> io::Result<usize> {
     let available_cubes = AvailableCubes {
         red: 12,
         green: 13,
         blue: 14,
     };
 
-    input
-        .par_lines()
-        .map(|line| {
-            let game_data = GameData::from(line);
-            game_data.check(&available_cubes)
-        })
-        .sum::<usize>()
+    sum_lines(reader, |line| {
+        let game_data = GameData::try_from(line)?;
+        Ok(game_data.check(&available_cubes))
+    })
 }
 
 #[aoc(day2, part2, FirstTry)]
 pub fn part2_first(input: &str) -> usize {
-    input
-        .par_lines()
-        .map(|line| {
-            let game_data = GameData::from(line);
-            game_data.calculate_minimum_cubes_mul()
-        })
-        .sum::<usize>()
+    part2_stream(input.as_bytes()).unwrap()
+}
+
+pub fn part2_stream<R: BufRead>(reader: R) -> io::Result<usize> {
+    sum_lines(reader, |line| {
+        let game_data = GameData::try_from(line)?;
+        Ok(game_data.calculate_minimum_cubes_mul())
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{part1_first, part2_first};
+    use super::{part1_first, part1_stream, part2_first, GameData};
+    use aoc_common::parsing::ParseError;
+    use std::io::BufReader;
 
     static SAMPLE: &str = r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
@@ -185,4 +198,26 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"#;
     fn test_part_2_first() {
         assert_eq!(part2_first(SAMPLE), 2286);
     }
+
+    #[test]
+    fn test_part_1_stream() {
+        let reader = BufReader::with_capacity(8, SAMPLE.as_bytes());
+        assert_eq!(part1_stream(reader).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_short_line_is_an_error() {
+        assert_eq!(
+            GameData::try_from("Ga:").unwrap_err(),
+            ParseError::Missing("game id")
+        );
+        assert_eq!(
+            GameData::try_from("Game 1").unwrap_err(),
+            ParseError::Missing("':' after game id")
+        );
+        assert_eq!(
+            GameData::try_from("Game x: 3 blue").unwrap_err(),
+            ParseError::InvalidNumber("x".to_string())
+        );
+    }
 }
@@ -0,0 +1,401 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+use std::str::FromStr;
+
+use aoc_common::helpers::crt;
+use aoc_common::parsing::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Broadcaster,
+    /// `%`, toggles on low pulses and sends its new state
+    FlipFlop,
+    /// `&`, sends low once the last pulse from every input was high
+    Conjunction,
+    /// Only ever named as an output, like `rx`
+    Sink,
+}
+
+#[derive(Debug, Clone)]
+struct Module {
+    name: String,
+    kind: Kind,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+}
+
+/// A pulse on its way from one module to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pulse {
+    pub from: usize,
+    pub to: usize,
+    pub high: bool,
+}
+
+/// Modules indexed by their position, plus the button pressing the
+/// broadcaster, which has no module of its own
+#[derive(Debug, Clone)]
+pub(crate) struct Network {
+    modules: Vec<Module>,
+    broadcaster: usize,
+    /// Flip-flops on
+    on: Vec<bool>,
+    /// For every conjunction, the last pulse from each of its inputs
+    memory: Vec<Vec<bool>>,
+    presses: u64,
+}
+
+/// The source of the broadcaster's pulses
+const BUTTON: usize = usize::MAX;
+
+/// The module called `name`, added as a sink the first time it comes up
+fn index<'a>(
+    name: &'a str,
+    indices: &mut HashMap<&'a str, usize>,
+    modules: &mut Vec<Module>,
+) -> usize {
+    *indices.entry(name).or_insert_with(|| {
+        modules.push(Module {
+            name: name.to_string(),
+            kind: Kind::Sink,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        });
+        modules.len() - 1
+    })
+}
+
+impl FromStr for Network {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modules = Vec::new();
+        let mut indices = HashMap::new();
+
+        for line in s.lines() {
+            let (module, outputs) = line
+                .split_once(" -> ")
+                .ok_or(ParseError::Missing("' -> ' after the module"))?;
+            let (kind, name) = match module.as_bytes().first() {
+                Some(b'%') => (Kind::FlipFlop, &module[1..]),
+                Some(b'&') => (Kind::Conjunction, &module[1..]),
+                _ if module == "broadcaster" => (Kind::Broadcaster, module),
+                _ => {
+                    let prefix = module.chars().next().unwrap_or(' ');
+                    return Err(ParseError::InvalidChar(prefix));
+                }
+            };
+
+            let from = index(name, &mut indices, &mut modules);
+            modules[from].kind = kind;
+            for output in outputs.split(", ") {
+                let to = index(output, &mut indices, &mut modules);
+                modules[from].outputs.push(to);
+                modules[to].inputs.push(from);
+            }
+        }
+
+        let broadcaster = modules
+            .iter()
+            .position(|module| module.kind == Kind::Broadcaster)
+            .ok_or(ParseError::Missing("broadcaster"))?;
+        let memory = modules
+            .iter()
+            .map(|module| vec![false; module.inputs.len()])
+            .collect();
+
+        Ok(Network {
+            on: vec![false; modules.len()],
+            memory,
+            modules,
+            broadcaster,
+            presses: 0,
+        })
+    }
+}
+
+impl Network {
+    fn index(&self, name: &str) -> Option<usize> {
+        self.modules.iter().position(|module| module.name == name)
+    }
+
+    pub fn name(&self, index: usize) -> &str {
+        if index == BUTTON {
+            "button"
+        } else {
+            &self.modules[index].name
+        }
+    }
+
+    /// Pushes the button once, handing every pulse to `observe` in the
+    /// order they're delivered
+    pub fn press(&mut self, mut observe: impl FnMut(&Pulse)) {
+        self.presses += 1;
+        let mut queue = VecDeque::from([Pulse {
+            from: BUTTON,
+            to: self.broadcaster,
+            high: false,
+        }]);
+
+        while let Some(pulse) = queue.pop_front() {
+            observe(&pulse);
+            let module = &self.modules[pulse.to];
+            let high = match module.kind {
+                Kind::Broadcaster => pulse.high,
+                Kind::FlipFlop if pulse.high => continue,
+                Kind::FlipFlop => {
+                    self.on[pulse.to] ^= true;
+                    self.on[pulse.to]
+                }
+                Kind::Conjunction => {
+                    let memory = &mut self.memory[pulse.to];
+                    let input = module.inputs.iter().position(|&i| i == pulse.from).unwrap();
+                    memory[input] = pulse.high;
+                    !memory.iter().all(|&high| high)
+                }
+                Kind::Sink => continue,
+            };
+
+            queue.extend(module.outputs.iter().map(|&to| Pulse {
+                from: pulse.to,
+                to,
+                high,
+            }));
+        }
+    }
+
+    /// Low and high pulses sent over `presses` presses
+    pub fn count_pulses(&mut self, presses: usize) -> (u64, u64) {
+        let (mut low, mut high) = (0, 0);
+        for _ in 0..presses {
+            self.press(|pulse| {
+                if pulse.high {
+                    high += 1;
+                } else {
+                    low += 1;
+                }
+            });
+        }
+        (low, high)
+    }
+
+    /// Presses until the first low pulse to `target`. It has to be
+    /// fed by a single conjunction, which sends low once all its inputs
+    /// are high on the same press: each input's high presses are watched
+    /// until they repeat, then the cycles are lined up.
+    pub fn first_low_to(&mut self, target: &str) -> Option<u64> {
+        let target = self.index(target)?;
+        let [feeder] = self.modules[target].inputs[..] else {
+            return None;
+        };
+        if self.modules[feeder].kind != Kind::Conjunction {
+            return None;
+        }
+
+        let inputs = self.modules[feeder].inputs.clone();
+        let mut seen: Vec<Vec<u64>> = vec![Vec::new(); inputs.len()];
+        while seen.iter().any(|presses| presses.len() < 2) {
+            if self.presses >= 1 << 20 {
+                return None;
+            }
+            let press = self.presses + 1;
+            self.press(|pulse| {
+                if pulse.to == feeder && pulse.high {
+                    let input = inputs.iter().position(|&i| i == pulse.from).unwrap();
+                    if seen[input].last() != Some(&press) {
+                        seen[input].push(press);
+                    }
+                }
+            });
+        }
+
+        let earliest = seen.iter().map(|presses| presses[0]).max().unwrap();
+        let cycles = seen
+            .iter()
+            .map(|presses| (presses[0], presses[1] - presses[0]));
+        let (remainder, modulus) = crt(cycles)?;
+        // The first press at or after every input's first high pulse
+        let behind = earliest.saturating_sub(remainder).div_ceil(modulus);
+        Some(remainder + behind * modulus)
+    }
+
+    /// The modules as a Graphviz digraph, flip-flops as boxes and
+    /// conjunctions as diamonds
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph modules {\n");
+        for module in &self.modules {
+            let shape = match module.kind {
+                Kind::Broadcaster => "doublecircle",
+                Kind::FlipFlop => "box",
+                Kind::Conjunction => "diamond",
+                Kind::Sink => "plaintext",
+            };
+            writeln!(dot, "    {} [shape={shape}];", module.name).unwrap();
+        }
+        for module in &self.modules {
+            for &output in &module.outputs {
+                writeln!(dot, "    {} -> {};", module.name, self.name(output)).unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+pub fn parse(input: &str) -> Result<Network, ParseError> {
+    Network::from_str(input)
+}
+
+/// Every pulse of the first `presses` presses as `a -high-> b`, a blank
+/// line after each press
+pub fn trace(input: &str, presses: usize) -> String {
+    let mut network = parse(input).unwrap();
+    let mut pulses = Vec::new();
+    for _ in 0..presses {
+        network.press(|&pulse| pulses.push(Some(pulse)));
+        pulses.push(None);
+    }
+
+    let mut out = String::new();
+    for pulse in pulses {
+        match pulse {
+            Some(pulse) => {
+                let level = if pulse.high { "high" } else { "low" };
+                let (from, to) = (network.name(pulse.from), network.name(pulse.to));
+                writeln!(out, "{from} -{level}-> {to}").unwrap();
+            }
+            None => out.push('\n'),
+        }
+    }
+    out
+}
+
+pub fn to_dot(input: &str) -> String {
+    parse(input).unwrap().to_dot()
+}
+
+#[aoc(day20, part1)]
+pub fn part_1(input: &str) -> u64 {
+    let (low, high) = parse(input).unwrap().count_pulses(1000);
+
+    low * high
+}
+
+#[aoc(day20, part2)]
+pub fn part_2(input: &str) -> u64 {
+    parse(input)
+        .unwrap()
+        .first_low_to("rx")
+        .expect("rx fed by a conjunction of cycling inputs")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE_1: &str = r#"broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a"#;
+
+    static SAMPLE_2: &str = r#"broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output"#;
+
+    /// `rx` gets its low pulse once `inv` and `b` are both high, on the
+    /// second press
+    static FEEDS_RX: &str = r#"broadcaster -> a
+%a -> inv, b
+&inv -> f
+%b -> f
+&f -> rx"#;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE_1), 32000000);
+        assert_eq!(part_1(SAMPLE_2), 11687500);
+    }
+
+    #[test]
+    fn test_count_pulses() {
+        let mut network = Network::from_str(SAMPLE_2).unwrap();
+        assert_eq!(network.count_pulses(1), (4, 4));
+        assert_eq!(network.count_pulses(1), (4, 2));
+    }
+
+    #[test]
+    fn test_first_low_to() {
+        let mut simulated = Network::from_str(FEEDS_RX).unwrap();
+        let rx = simulated.index("rx").unwrap();
+        let mut presses = 0;
+        let mut low = false;
+        while !low {
+            presses += 1;
+            simulated.press(|pulse| low |= pulse.to == rx && !pulse.high);
+        }
+
+        assert_eq!(part_2(FEEDS_RX), presses);
+        assert_eq!(presses, 2);
+        let mut network = Network::from_str(SAMPLE_2).unwrap();
+        assert_eq!(network.first_low_to("rx"), None);
+    }
+
+    #[test]
+    fn test_trace() {
+        assert_eq!(
+            trace(SAMPLE_2, 2),
+            "button -low-> broadcaster\n\
+             broadcaster -low-> a\n\
+             a -high-> inv\n\
+             a -high-> con\n\
+             inv -low-> b\n\
+             con -high-> output\n\
+             b -high-> con\n\
+             con -low-> output\n\
+             \n\
+             button -low-> broadcaster\n\
+             broadcaster -low-> a\n\
+             a -low-> inv\n\
+             a -low-> con\n\
+             inv -high-> b\n\
+             con -high-> output\n\
+             \n"
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        assert_eq!(
+            to_dot(SAMPLE_1),
+            "digraph modules {\n    \
+             broadcaster [shape=doublecircle];\n    \
+             a [shape=box];\n    \
+             b [shape=box];\n    \
+             c [shape=box];\n    \
+             inv [shape=diamond];\n    \
+             broadcaster -> a;\n    \
+             broadcaster -> b;\n    \
+             broadcaster -> c;\n    \
+             a -> b;\n    \
+             b -> c;\n    \
+             c -> inv;\n    \
+             inv -> a;\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_module_is_an_error() {
+        assert_eq!(
+            Network::from_str("broadcaster -> a\n$a -> b").unwrap_err(),
+            ParseError::InvalidChar('$')
+        );
+        assert_eq!(
+            Network::from_str("%a -> b").unwrap_err(),
+            ParseError::Missing("broadcaster")
+        );
+    }
+}
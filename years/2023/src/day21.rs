@@ -0,0 +1,205 @@
+use std::collections::{HashSet, VecDeque};
+use std::str::FromStr;
+
+use aoc_common::grid::{Direction, Grid, Pos};
+use aoc_common::helpers::extrapolate;
+use aoc_common::parsing::ParseError;
+
+/// Garden plots `.`, rocks `#` and the starting plot `S`
+#[derive(Debug)]
+pub(crate) struct Garden {
+    rocks: Grid<bool>,
+    start: Pos,
+}
+
+impl FromStr for Garden {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_only(s, ".#S")?;
+        let start = grid
+            .position(|&cell| cell == b'S')
+            .ok_or(ParseError::Missing("start plot 'S'"))?;
+
+        Ok(Garden {
+            rocks: grid.map(|_, &cell| cell == b'#'),
+            start,
+        })
+    }
+}
+
+/// Plots reachable in exactly `steps` steps out of those first reached
+/// after `distances`: stepping back and forth, a plot reached after `d`
+/// steps is reachable again after `d + 2`, `d + 4`, ...
+fn reachable(distances: impl Iterator<Item = usize>, steps: usize) -> u64 {
+    distances
+        .filter(|&distance| distance <= steps && distance % 2 == steps % 2)
+        .count() as u64
+}
+
+impl Garden {
+    /// Steps to every plot of the map on its own, walled in at the edges
+    pub fn distances(&self) -> Grid<Option<usize>> {
+        let mut distances = Grid::new(self.rocks.width(), self.rocks.height(), None);
+        let mut queue = VecDeque::from([(self.start, 0)]);
+        distances[self.start] = Some(0);
+
+        while let Some((pos, distance)) = queue.pop_front() {
+            for (_, next) in self.rocks.neighbours(pos) {
+                if !self.rocks[next] && distances[next].is_none() {
+                    distances[next] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Plots reachable in exactly `steps` steps on the map on its own
+    pub fn reachable(&self, steps: usize) -> u64 {
+        reachable(self.distances().iter().filter_map(|(_, &d)| d), steps)
+    }
+
+    /// Plots reachable in exactly each of `steps` on the map repeated
+    /// forever in every direction, by one search out to the largest
+    pub fn reachable_tiled(&self, steps: &[usize]) -> Vec<u64> {
+        let (width, height) = (self.rocks.width() as i64, self.rocks.height() as i64);
+        let max_steps = steps.iter().copied().max().unwrap_or(0);
+        let start = (self.start.x as i64, self.start.y as i64);
+
+        let mut seen = HashSet::from([start]);
+        let mut distances = vec![0];
+        let mut frontier = vec![start];
+        for distance in 1..=max_steps {
+            let mut next_frontier = Vec::new();
+            for &(x, y) in &frontier {
+                for direction in Direction::ALL {
+                    let (dx, dy) = direction.delta();
+                    let next = (x + dx, y + dy);
+                    let tile = Pos::new(
+                        next.0.rem_euclid(width) as usize,
+                        next.1.rem_euclid(height) as usize,
+                    );
+                    if !self.rocks[tile] && seen.insert(next) {
+                        next_frontier.push(next);
+                    }
+                }
+            }
+            distances.extend(std::iter::repeat_n(distance, next_frontier.len()));
+            frontier = next_frontier;
+        }
+
+        steps
+            .iter()
+            .map(|&steps| reachable(distances.iter().copied(), steps))
+            .collect()
+    }
+
+    /// Plots reachable in exactly `steps` steps on the repeated map, for a
+    /// square map with the start in the middle and clear paths from it to
+    /// the edges. The reachable area then grows with whole tiles every
+    /// `width` steps, and its plot count is a quadratic in the number of
+    /// tiles crossed: three samples, one tile apart, fix it.
+    pub fn reachable_extrapolated(&self, steps: usize) -> u64 {
+        let width = self.rocks.width();
+        let (tiles, offset) = (steps / width, steps % width);
+        let samples = self.reachable_tiled(&[offset, offset + width, offset + 2 * width]);
+        if tiles < 3 {
+            return samples[tiles];
+        }
+
+        let mut window = [0, 1, 2].map(|i| samples[i] as i64);
+        for _ in 2..tiles {
+            let next = extrapolate(&mut window.clone());
+            window = [window[1], window[2], next];
+        }
+        window[2] as u64
+    }
+}
+
+pub fn parse(input: &str) -> Result<Garden, ParseError> {
+    Garden::from_str(input)
+}
+
+#[aoc(day21, part1)]
+pub fn part_1(input: &str) -> u64 {
+    parse(input).unwrap().reachable(64)
+}
+
+#[aoc(day21, part2)]
+pub fn part_2(input: &str) -> u64 {
+    parse(input).unwrap().reachable_extrapolated(26501365)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = r#"...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+..........."#;
+
+    /// Clear middle row, column and border, like the real inputs
+    static OPEN: &str = r#"...........
+.##..#..#..
+..#..#...#.
+.#...#.##..
+...#.....#.
+.....S.....
+.#.......#.
+..#..#..#..
+...#.#.##..
+.#...#...#.
+..........."#;
+
+    #[test]
+    fn test_reachable() {
+        let garden = Garden::from_str(SAMPLE).unwrap();
+        assert_eq!(garden.reachable(1), 2);
+        assert_eq!(garden.reachable(2), 4);
+        assert_eq!(garden.reachable(3), 6);
+        assert_eq!(garden.reachable(6), 16);
+    }
+
+    #[test]
+    fn test_reachable_tiled() {
+        let garden = Garden::from_str(SAMPLE).unwrap();
+        assert_eq!(
+            garden.reachable_tiled(&[6, 10, 50, 100]),
+            vec![16, 50, 1594, 6536]
+        );
+    }
+
+    #[test]
+    fn test_extrapolation_matches_search() {
+        let garden = Garden::from_str(OPEN).unwrap();
+        for steps in [5, 16, 27, 38, 49, 60, 71, 30, 52] {
+            assert_eq!(
+                garden.reachable_extrapolated(steps),
+                garden.reachable_tiled(&[steps])[0],
+                "{steps} steps"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_garden_is_an_error() {
+        assert_eq!(
+            Garden::from_str("..\n.#").unwrap_err(),
+            ParseError::Missing("start plot 'S'")
+        );
+        assert_eq!(
+            Garden::from_str("S.\n.").unwrap_err(),
+            ParseError::RaggedRow(1)
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use aoc_common::execution::map_sum;
+use aoc_common::grid::{Grid, Pos};
+use aoc_common::parsing::{parse_number, ParseError};
+
+/// A brick from corner `start` to corner `end`, both inclusive, as
+/// `[x, y, z]` with `z` going up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Brick {
+    pub start: [usize; 3],
+    pub end: [usize; 3],
+}
+
+impl FromStr for Brick {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('~')
+            .ok_or(ParseError::Missing("'~' between the brick's ends"))?;
+        let corner = |s: &str| -> Result<[usize; 3], ParseError> {
+            let coordinates: Vec<usize> =
+                s.split(',').map(parse_number).collect::<Result<_, _>>()?;
+            coordinates
+                .try_into()
+                .map_err(|_| ParseError::Missing("three coordinates per end"))
+        };
+        let (a, b) = (corner(start)?, corner(end)?);
+        // z = 0 is the ground
+        if a[2] == 0 || b[2] == 0 {
+            return Err(ParseError::Invalid("brick in the ground"));
+        }
+
+        // Either corner may come first
+        Ok(Brick {
+            start: [0, 1, 2].map(|i| a[i].min(b[i])),
+            end: [0, 1, 2].map(|i| a[i].max(b[i])),
+        })
+    }
+}
+
+impl Brick {
+    fn footprint(&self) -> impl Iterator<Item = Pos> + '_ {
+        (self.start[1]..=self.end[1])
+            .flat_map(move |y| (self.start[0]..=self.end[0]).map(move |x| Pos::new(x, y)))
+    }
+}
+
+/// The bricks once they have all come to rest, indexed as in the snapshot,
+/// with which bricks each one rests on and holds up
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SupportGraph {
+    pub bricks: Vec<Brick>,
+    /// The bricks resting directly on each brick
+    pub supports: Vec<Vec<usize>>,
+    /// The bricks each brick rests directly on, none for the ground
+    pub supported_by: Vec<Vec<usize>>,
+}
+
+impl SupportGraph {
+    /// Drops the bricks lowest first onto a height map of the tallest brick
+    /// below every column, remembering which brick that is. Bricks that
+    /// overlap in the snapshot are an error.
+    pub fn settle(mut bricks: Vec<Brick>) -> Result<Self, ParseError> {
+        let width = bricks
+            .iter()
+            .map(|brick| brick.end[0] + 1)
+            .max()
+            .unwrap_or(0);
+        let depth = bricks
+            .iter()
+            .map(|brick| brick.end[1] + 1)
+            .max()
+            .unwrap_or(0);
+        let mut top: Grid<(usize, Option<usize>)> = Grid::new(width, depth, (0, None));
+        let mut supports = vec![Vec::new(); bricks.len()];
+        let mut supported_by = vec![Vec::new(); bricks.len()];
+
+        let mut order: Vec<usize> = (0..bricks.len()).collect();
+        order.sort_by_key(|&i| bricks[i].start[2]);
+        for i in order {
+            let brick = bricks[i];
+            let floor = brick.footprint().map(|pos| top[pos].0).max().unwrap();
+            for pos in brick.footprint() {
+                if let (height, Some(below)) = top[pos] {
+                    if height == floor && !supported_by[i].contains(&below) {
+                        supported_by[i].push(below);
+                        supports[below].push(i);
+                    }
+                }
+            }
+
+            let drop = brick.start[2]
+                .checked_sub(floor + 1)
+                .ok_or(ParseError::Invalid("overlapping bricks"))?;
+            bricks[i].start[2] -= drop;
+            bricks[i].end[2] -= drop;
+            for pos in brick.footprint() {
+                top[pos] = (bricks[i].end[2], Some(i));
+            }
+        }
+
+        Ok(SupportGraph {
+            bricks,
+            supports,
+            supported_by,
+        })
+    }
+
+    /// Whether removing brick `i` leaves every brick it holds up resting
+    /// on another one
+    pub fn is_safe(&self, i: usize) -> bool {
+        self.supports[i]
+            .iter()
+            .all(|&above| self.supported_by[above].len() > 1)
+    }
+
+    /// How many other bricks fall when brick `i` is removed, each brick
+    /// falling once everything it rested on has fallen
+    pub fn chain_reaction(&self, i: usize) -> usize {
+        let mut remaining: Vec<usize> = self.supported_by.iter().map(Vec::len).collect();
+        let mut falling = VecDeque::from([i]);
+        let mut fallen = 0;
+
+        while let Some(brick) = falling.pop_front() {
+            for &above in &self.supports[brick] {
+                remaining[above] -= 1;
+                if remaining[above] == 0 {
+                    fallen += 1;
+                    falling.push_back(above);
+                }
+            }
+        }
+
+        fallen
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("support graph is always serializable")
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Brick>, ParseError> {
+    input.lines().map(Brick::from_str).collect()
+}
+
+pub fn support_graph(input: &str) -> SupportGraph {
+    SupportGraph::settle(parse(input).unwrap()).unwrap()
+}
+
+#[aoc(day22, part1)]
+pub fn part_1(input: &str) -> usize {
+    let graph = support_graph(input);
+
+    (0..graph.bricks.len())
+        .filter(|&i| graph.is_safe(i))
+        .count()
+}
+
+#[aoc(day22, part2)]
+pub fn part_2(input: &str) -> usize {
+    let graph = support_graph(input);
+
+    map_sum(0..graph.bricks.len(), |i| graph.chain_reaction(i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = r#"1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9"#;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE), 5);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(SAMPLE), 7);
+    }
+
+    #[test]
+    fn test_support_graph() {
+        let graph = support_graph(SAMPLE);
+        assert_eq!(
+            graph.supports,
+            vec![
+                vec![1, 2],
+                vec![3, 4],
+                vec![3, 4],
+                vec![5],
+                vec![5],
+                vec![6],
+                vec![]
+            ]
+        );
+        assert_eq!(graph.supported_by[3], vec![1, 2]);
+        assert_eq!(
+            graph.bricks[6],
+            Brick {
+                start: [1, 1, 5],
+                end: [1, 1, 6]
+            }
+        );
+        assert_eq!(graph.chain_reaction(0), 6);
+        assert_eq!(graph.chain_reaction(5), 1);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&support_graph(SAMPLE).to_json()).unwrap();
+        assert_eq!(json["bricks"][1]["start"], serde_json::json!([0, 0, 2]));
+        assert_eq!(json["supports"][0], serde_json::json!([1, 2]));
+        assert_eq!(json["supported_by"][0], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_invalid_brick_is_an_error() {
+        assert_eq!(
+            Brick::from_str("1,0,1-1,2,1").unwrap_err(),
+            ParseError::Missing("'~' between the brick's ends")
+        );
+        assert_eq!(
+            Brick::from_str("1,0~1,2,1").unwrap_err(),
+            ParseError::Missing("three coordinates per end")
+        );
+        assert_eq!(
+            Brick::from_str("1,0,0~1,0,0").unwrap_err(),
+            ParseError::Invalid("brick in the ground")
+        );
+        assert_eq!(
+            Brick::from_str("2,1,5~1,1,3").unwrap(),
+            Brick {
+                start: [1, 1, 3],
+                end: [2, 1, 5]
+            }
+        );
+    }
+
+    #[test]
+    fn test_overlapping_bricks_are_an_error() {
+        let bricks = parse("1,0,1~1,0,3\n1,0,2~1,0,2").unwrap();
+        assert_eq!(
+            SupportGraph::settle(bricks).unwrap_err(),
+            ParseError::Invalid("overlapping bricks")
+        );
+    }
+}
@@ -0,0 +1,364 @@
+use std::{fmt, str::FromStr};
+
+use aoc_common::execution::map_reduce;
+use aoc_common::grid::{Direction, Grid, Pos};
+use aoc_common::parsing::ParseError;
+
+/// Paths `.`, forest `#` and slopes `^>v<`, entered at the gap in the top
+/// row and left at the one in the bottom row
+#[derive(Debug)]
+pub(crate) struct Trails {
+    tiles: Grid<u8>,
+    start: Pos,
+    end: Pos,
+}
+
+impl FromStr for Trails {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = Grid::parse_only(s, ".#^>v<")?;
+        let gap = |y| {
+            (0..tiles.width())
+                .map(|x| Pos::new(x, y))
+                .find(|&pos| tiles[pos] == b'.')
+        };
+        let start = gap(0).ok_or(ParseError::Missing("gap in the top row"))?;
+        let end = gap(tiles.height() - 1).ok_or(ParseError::Missing("gap in the bottom row"))?;
+
+        Ok(Trails { tiles, start, end })
+    }
+}
+
+/// The trails between junctions, the tiles with three or more ways out,
+/// each edge a corridor from one to the next and its length in steps
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    pub junctions: Vec<Pos>,
+    pub edges: Vec<Vec<(usize, usize)>>,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Trails {
+    /// Whether a step in `direction` onto `pos` is allowed: not into the
+    /// forest, and only down a slope when `slopes` count
+    fn can_step(&self, pos: Pos, direction: Direction, slopes: bool) -> bool {
+        match self.tiles[pos] {
+            b'#' => false,
+            b'.' => true,
+            _ if !slopes => true,
+            slope => Direction::ALL[b"^>v<".iter().position(|&s| s == slope).unwrap()] == direction,
+        }
+    }
+
+    fn is_junction(&self, pos: Pos) -> bool {
+        pos == self.start
+            || pos == self.end
+            || self
+                .tiles
+                .neighbours(pos)
+                .filter(|&(_, next)| self.tiles[next] != b'#')
+                .count()
+                >= 3
+    }
+
+    /// Walks every corridor out of every junction to the junction at its
+    /// other end, dropping dead ends and corridors against a slope
+    pub fn graph(&self, slopes: bool) -> Graph {
+        let junctions: Vec<Pos> = self
+            .tiles
+            .positions()
+            .filter(|&pos| self.tiles[pos] != b'#' && self.is_junction(pos))
+            .collect();
+        let index = |pos| junctions.iter().position(|&junction| junction == pos);
+
+        let edges = junctions
+            .iter()
+            .map(|&from| {
+                let mut edges = Vec::new();
+                for (direction, next) in self.tiles.neighbours(from) {
+                    if !self.can_step(next, direction, slopes) {
+                        continue;
+                    }
+                    let (mut previous, mut pos, mut length) = (from, next, 1);
+                    loop {
+                        if let Some(to) = index(pos) {
+                            edges.push((to, length));
+                            break;
+                        }
+                        let step = self.tiles.neighbours(pos).find(|&(direction, next)| {
+                            next != previous && self.can_step(next, direction, slopes)
+                        });
+                        let Some((_, next)) = step else {
+                            break;
+                        };
+                        (previous, pos, length) = (pos, next, length + 1);
+                    }
+                }
+                edges
+            })
+            .collect();
+
+        Graph {
+            start: index(self.start).unwrap(),
+            end: index(self.end).unwrap(),
+            junctions,
+            edges,
+        }
+    }
+}
+
+/// The junctions a hike has been to, one bit each
+trait Visited: Clone + Send {
+    fn empty(junctions: usize) -> Self;
+    fn contains(&self, junction: usize) -> bool;
+    fn toggle(&mut self, junction: usize);
+}
+
+impl Visited for u64 {
+    fn empty(_: usize) -> Self {
+        0
+    }
+
+    fn contains(&self, junction: usize) -> bool {
+        self & (1 << junction) != 0
+    }
+
+    fn toggle(&mut self, junction: usize) {
+        *self ^= 1 << junction;
+    }
+}
+
+/// A word per 64 junctions, for graphs too big for a single `u64`
+impl Visited for Vec<u64> {
+    fn empty(junctions: usize) -> Self {
+        vec![0; junctions.div_ceil(64)]
+    }
+
+    fn contains(&self, junction: usize) -> bool {
+        self[junction / 64] & (1 << (junction % 64)) != 0
+    }
+
+    fn toggle(&mut self, junction: usize) {
+        self[junction / 64] ^= 1 << (junction % 64);
+    }
+}
+
+/// A hike so far: where it is, the junctions it has been to and its length
+#[derive(Debug, Clone)]
+struct Hike<V> {
+    at: usize,
+    visited: V,
+    length: usize,
+}
+
+impl Graph {
+    /// Every way on from `hike` to a junction not visited yet
+    fn extend<'a, V: Visited>(&'a self, hike: &'a Hike<V>) -> impl Iterator<Item = Hike<V>> + 'a {
+        self.edges[hike.at]
+            .iter()
+            .filter(|&&(to, _)| !hike.visited.contains(to))
+            .map(|&(to, length)| {
+                let mut visited = hike.visited.clone();
+                visited.toggle(to);
+                Hike {
+                    at: to,
+                    visited,
+                    length: hike.length + length,
+                }
+            })
+    }
+
+    /// The longest way to the end from `at`, depth first, marking and
+    /// unmarking junctions in `visited` on the way
+    fn longest_from<V: Visited>(&self, at: usize, length: usize, visited: &mut V) -> Option<usize> {
+        if at == self.end {
+            return Some(length);
+        }
+        let mut longest = None;
+        for &(to, steps) in &self.edges[at] {
+            if visited.contains(to) {
+                continue;
+            }
+            visited.toggle(to);
+            longest = longest.max(self.longest_from(to, length + steps, visited));
+            visited.toggle(to);
+        }
+        longest
+    }
+
+    /// The longest hike from start to end that visits no junction twice,
+    /// with a `u64` for the visited junctions when they all fit in one
+    pub fn longest_hike(&self) -> Option<usize> {
+        if self.junctions.len() <= 64 {
+            self.search::<u64>()
+        } else {
+            self.search::<Vec<u64>>()
+        }
+    }
+
+    /// The first few junctions are expanded breadth first, until there
+    /// are enough partial hikes to share out, then each is searched depth
+    /// first on its own.
+    fn search<V: Visited>(&self) -> Option<usize> {
+        let mut visited = V::empty(self.junctions.len());
+        visited.toggle(self.start);
+        let mut hikes = vec![Hike {
+            at: self.start,
+            visited,
+            length: 0,
+        }];
+        let mut finished = None;
+        for _ in 0..6 {
+            let (done, open): (Vec<_>, Vec<_>) = hikes
+                .iter()
+                .flat_map(|hike| self.extend(hike))
+                .partition(|hike| hike.at == self.end);
+            finished = finished.max(done.iter().map(|hike| hike.length).max());
+            hikes = open;
+        }
+
+        map_reduce(
+            hikes,
+            |mut hike| self.longest_from(hike.at, hike.length, &mut hike.visited),
+            || None,
+            Ord::max,
+        )
+        .max(finished)
+    }
+}
+
+/// One line per junction, `4 (5, 13): 5 [12], 6 [38]` for the junctions
+/// it leads to and the length of the way there
+impl fmt::Display for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (pos, edges)) in self.junctions.iter().zip(&self.edges).enumerate() {
+            write!(f, "{i} ({}, {}):", pos.x, pos.y)?;
+            for (j, (to, length)) in edges.iter().enumerate() {
+                let separator = if j == 0 { " " } else { ", " };
+                write!(f, "{separator}{to} [{length}]")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn parse(input: &str) -> Result<Trails, ParseError> {
+    Trails::from_str(input)
+}
+
+pub fn render(input: &str, slopes: bool) -> String {
+    parse(input).unwrap().graph(slopes).to_string()
+}
+
+pub fn longest_hike(input: &str, slopes: bool) -> usize {
+    parse(input)
+        .unwrap()
+        .graph(slopes)
+        .longest_hike()
+        .expect("a way from the start to the end")
+}
+
+#[aoc(day23, part1)]
+pub fn part_1(input: &str) -> usize {
+    longest_hike(input, true)
+}
+
+#[aoc(day23, part2)]
+pub fn part_2(input: &str) -> usize {
+    longest_hike(input, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::execution::Execution;
+
+    static SAMPLE: &str = r#"#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###v#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#"#;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE), 94);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(SAMPLE), 154);
+    }
+
+    #[test]
+    fn test_execution_modes_agree() {
+        let graph = Trails::from_str(SAMPLE).unwrap().graph(false);
+        for execution in [Execution::Parallel, Execution::Sequential] {
+            assert_eq!(execution.install(|| graph.longest_hike()), Some(154));
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = render(SAMPLE, true);
+        assert_eq!(rendered.lines().count(), 9);
+        assert!(rendered.starts_with("0 (1, 0): 2 [15]\n1 (11, 3): 3 [30], 5 [24]\n"));
+        // Walking back from the end runs up a slope
+        assert!(rendered.ends_with("7 (19, 19): 8 [5]\n8 (21, 22):\n"));
+    }
+
+    #[test]
+    fn test_no_way_through() {
+        let graph = Trails::from_str("#.#\n#v#\n#.#").unwrap().graph(true);
+        assert_eq!(graph.longest_hike(), Some(2));
+        let graph = Trails::from_str("#.#\n#^#\n#.#").unwrap().graph(true);
+        assert_eq!(graph.longest_hike(), None);
+    }
+
+    #[test]
+    fn test_more_junctions_than_a_word() {
+        // A corridor with a dead end off every other tile, each a junction
+        let mut map = String::from("#.##\n");
+        for _ in 0..70 {
+            map.push_str("#..#\n#.##\n");
+        }
+        let graph = Trails::from_str(map.trim_end()).unwrap().graph(false);
+        assert_eq!(graph.junctions.len(), 72);
+        assert_eq!(graph.longest_hike(), Some(140));
+        // The word bitset agrees with the single word on a small graph
+        let graph = Trails::from_str(SAMPLE).unwrap().graph(false);
+        assert_eq!(graph.search::<Vec<u64>>(), Some(154));
+    }
+
+    #[test]
+    fn test_invalid_map_is_an_error() {
+        assert_eq!(
+            Trails::from_str("###\n#.#\n#.#").unwrap_err(),
+            ParseError::Missing("gap in the top row")
+        );
+        assert_eq!(
+            Trails::from_str("#.#\n#.#\n###").unwrap_err(),
+            ParseError::Missing("gap in the bottom row")
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use aoc_common::helpers::extended_gcd;
+use aoc_common::parsing::{parse_number, ParseError};
+
+/// Position and velocity per nanosecond, `[x, y, z]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    pub position: [i128; 3],
+    pub velocity: [i128; 3],
+}
+
+impl FromStr for Hailstone {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (position, velocity) = s
+            .split_once('@')
+            .ok_or(ParseError::Missing("'@' between position and velocity"))?;
+        let vector = |s: &str| -> Result<[i128; 3], ParseError> {
+            let coordinates: Vec<i128> = s
+                .split(',')
+                .map(|coordinate| parse_number(coordinate.trim()))
+                .collect::<Result<_, _>>()?;
+            coordinates
+                .try_into()
+                .map_err(|_| ParseError::Missing("three coordinates"))
+        };
+
+        Ok(Hailstone {
+            position: vector(position)?,
+            velocity: vector(velocity)?,
+        })
+    }
+}
+
+/// Whether the paths of `a` and `b` in the x-y plane cross inside `area`
+/// on both axes, later than now for both. Everything stays an integer:
+/// the crossing is at `a.position + t * a.velocity` with `t = t_num / det`,
+/// compared after multiplying through by `det`.
+fn paths_cross(a: &Hailstone, b: &Hailstone, area: &RangeInclusive<i128>) -> bool {
+    let ([px, py, _], [vx, vy, _]) = (a.position, a.velocity);
+    let ([qx, qy, _], [wx, wy, _]) = (b.position, b.velocity);
+
+    let det = vx * wy - vy * wx;
+    if det == 0 {
+        // Parallel paths
+        return false;
+    }
+    let (dx, dy) = (qx - px, qy - py);
+    let (mut t_num, mut s_num) = (dx * wy - dy * wx, dx * vy - dy * vx);
+    let mut det = det;
+    if det < 0 {
+        (det, t_num, s_num) = (-det, -t_num, -s_num);
+    }
+    if t_num < 0 || s_num < 0 {
+        return false;
+    }
+
+    let (low, high) = (area.start() * det, area.end() * det);
+    let (x, y) = (px * det + vx * t_num, py * det + vy * t_num);
+    (low..=high).contains(&x) && (low..=high).contains(&y)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Hailstone>, ParseError> {
+    input.lines().map(Hailstone::from_str).collect()
+}
+
+pub fn crossings(input: &str, area: RangeInclusive<i128>) -> usize {
+    let hailstones = parse(input).unwrap();
+
+    hailstones
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            hailstones[i + 1..]
+                .iter()
+                .filter(|b| paths_cross(a, b, &area))
+                .count()
+        })
+        .sum()
+}
+
+fn cross(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn sub(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+/// The three equations, linear in the rock's position `P` and velocity
+/// `V`, that hailstones `a` and `b` give: the rock meets each one, so
+/// `(P - p) × (V - v) = 0` for both, and the difference of the two drops
+/// the `P × V` term:
+/// `P × (vb - va) + (pb - pa) × V = pb × vb - pa × va`.
+/// Each row is the coefficients of `[Px, Py, Pz, Vx, Vy, Vz]` and the
+/// right hand side.
+fn equations(a: &Hailstone, b: &Hailstone) -> [[i128; 7]; 3] {
+    let dv = sub(b.velocity, a.velocity);
+    let dp = sub(b.position, a.position);
+    let rhs = sub(cross(b.position, b.velocity), cross(a.position, a.velocity));
+
+    // P × dv as a matrix acting on P is [[0, dvz, -dvy], [-dvz, 0, dvx], [dvy, -dvx, 0]],
+    // dp × V acting on V is [[0, -dpz, dpy], [dpz, 0, -dpx], [-dpy, dpx, 0]]
+    [
+        [0, dv[2], -dv[1], 0, -dp[2], dp[1], rhs[0]],
+        [-dv[2], 0, dv[0], dp[2], 0, -dp[0], rhs[1]],
+        [dv[1], -dv[0], 0, -dp[1], dp[0], 0, rhs[2]],
+    ]
+}
+
+/// Solves the linear system modulo the prime `p`, by Gauss-Jordan
+/// elimination. `None` when it is singular modulo `p`.
+fn solve_mod(rows: &[[i128; 7]; 6], p: i128) -> Option<[i128; 6]> {
+    let mut m = rows.map(|row| row.map(|value| value.rem_euclid(p)));
+    let inverse = |a: i128| extended_gcd(a, p).1.rem_euclid(p);
+
+    for column in 0..6 {
+        let pivot = (column..6).find(|&row| m[row][column] != 0)?;
+        m.swap(column, pivot);
+        let scale = inverse(m[column][column]);
+        for value in &mut m[column] {
+            *value = *value * scale % p;
+        }
+        let pivot_row = m[column];
+        for (row, values) in m.iter_mut().enumerate() {
+            let factor = values[column];
+            if row == column || factor == 0 {
+                continue;
+            }
+            for (value, &pivot) in values.iter_mut().zip(&pivot_row) {
+                *value = (*value - factor * pivot).rem_euclid(p);
+            }
+        }
+    }
+
+    Some(m.map(|row| row[6]))
+}
+
+/// Whether `rock` hits `hailstone` at a whole nanosecond from now on
+fn hits(rock: &Hailstone, hailstone: &Hailstone) -> bool {
+    let gap = sub(hailstone.position, rock.position);
+    let closing = sub(rock.velocity, hailstone.velocity);
+    let Some(axis) = (0..3).find(|&axis| closing[axis] != 0) else {
+        return gap == [0; 3];
+    };
+    if gap[axis] % closing[axis] != 0 {
+        return false;
+    }
+    let t = gap[axis] / closing[axis];
+
+    t >= 0 && (0..3).all(|axis| gap[axis] == t * closing[axis])
+}
+
+/// Primes just below `2^62`: residues are in `(-2^61, 2^61)` once centred,
+/// far beyond any rock coordinate, and products of two still fit an `i128`
+const PRIMES: [i128; 2] = [(1 << 62) - 57, (1 << 62) - 87];
+
+/// The rock trajectory hitting every hailstone. The answer is an integer
+/// vector but the system's minors are far too big for `i128`, so it is
+/// solved exactly modulo a large prime, read back as the centred residue
+/// and then checked against every hailstone in plain integers.
+pub fn rock(hailstones: &[Hailstone]) -> Option<Hailstone> {
+    let candidates = hailstones.iter().take(6).collect::<Vec<_>>();
+
+    for &p in &PRIMES {
+        for (j, b) in candidates.iter().enumerate().skip(1) {
+            for c in &candidates[j + 1..] {
+                let a = candidates[0];
+                let [e1, e2, e3] = equations(a, b);
+                let [e4, e5, e6] = equations(a, c);
+                let Some(solution) = solve_mod(&[e1, e2, e3, e4, e5, e6], p) else {
+                    continue;
+                };
+
+                let centred = solution.map(|value| if value > p / 2 { value - p } else { value });
+                let rock = Hailstone {
+                    position: [centred[0], centred[1], centred[2]],
+                    velocity: [centred[3], centred[4], centred[5]],
+                };
+                if hailstones.iter().all(|hailstone| hits(&rock, hailstone)) {
+                    return Some(rock);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[aoc(day24, part1)]
+pub fn part_1(input: &str) -> usize {
+    crossings(input, 200_000_000_000_000..=400_000_000_000_000)
+}
+
+#[aoc(day24, part2)]
+pub fn part_2(input: &str) -> i128 {
+    let hailstones = parse(input).unwrap();
+    let rock = rock(&hailstones).expect("a rock hitting every hailstone");
+
+    rock.position.iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE: &str = r#"19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3"#;
+
+    #[test]
+    fn test_crossings() {
+        assert_eq!(crossings(SAMPLE, 7..=27), 2);
+        assert_eq!(crossings(SAMPLE, 0..=100), 3);
+    }
+
+    #[test]
+    fn test_paths_cross() {
+        let hailstones = parse(SAMPLE).unwrap();
+        let area = 7..=27;
+        // Inside, outside, parallel, and in the first hailstone's past
+        assert!(paths_cross(&hailstones[0], &hailstones[1], &area));
+        assert!(!paths_cross(&hailstones[0], &hailstones[3], &area));
+        assert!(!paths_cross(&hailstones[1], &hailstones[2], &area));
+        assert!(!paths_cross(&hailstones[0], &hailstones[4], &area));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(SAMPLE), 47);
+    }
+
+    #[test]
+    fn test_rock() {
+        let hailstones = parse(SAMPLE).unwrap();
+        assert_eq!(
+            rock(&hailstones),
+            Some(Hailstone {
+                position: [24, 13, 10],
+                velocity: [-3, 1, 2]
+            })
+        );
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        // A rock far out, with hailstones at real input magnitudes
+        let rock_path = Hailstone {
+            position: [
+                287_430_900_705_823,
+                451_620_998_712_421,
+                260_730_677_041_648,
+            ],
+            velocity: [-63, -300, 91],
+        };
+        let hailstones: Vec<Hailstone> = [
+            ([12, -97, 33], 523_407_421_513),
+            ([-41, 28, 120], 901_874_034_102),
+            ([86, 15, -77], 310_556_292_930),
+            ([7, -240, 64], 744_120_019_871),
+        ]
+        .iter()
+        .map(|&(velocity, t)| Hailstone {
+            position: [0, 1, 2].map(|axis| {
+                rock_path.position[axis] + t * (rock_path.velocity[axis] - velocity[axis])
+            }),
+            velocity,
+        })
+        .collect();
+
+        assert_eq!(rock(&hailstones), Some(rock_path));
+    }
+
+    #[test]
+    fn test_invalid_hailstone_is_an_error() {
+        assert_eq!(
+            "1, 2, 3 -1, 0, 2".parse::<Hailstone>().unwrap_err(),
+            ParseError::Missing("'@' between position and velocity")
+        );
+        assert_eq!(
+            "1, 2 @ -1, 0, 2".parse::<Hailstone>().unwrap_err(),
+            ParseError::Missing("three coordinates")
+        );
+    }
+}
@@ -1,7 +1,8 @@
-use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 
+use aoc_common::execution::map_sum;
+
 const ADJACENCY_CORDS: &[isize] = &[-1, 0, 1];
 
 #[derive(Debug, Hash, Eq, PartialEq)]
@@ -23,13 +24,13 @@ struct Numbers(HashMap<Coords, u32>);
 struct Symbols(HashMap<Coords, char>);
 
 #[derive(Debug)]
-struct Grid {
+pub(crate) struct Grid {
     numbers: Numbers,
     symbols: Symbols,
 }
 
 impl Grid {
-    fn new(input: &str) -> Self {
+    pub(crate) fn new(input: &str) -> Self {
         let mut numbers = Numbers::default();
         let mut symbols = Symbols::default();
 
@@ -93,11 +94,33 @@ impl Grid {
 
     /// Iterate over symbols and find adjacent numbers (even diagonally), sum them up and return
     fn find_sum_of_numbers_adjacent_to_symbols(&self) -> u32 {
-        self.symbols
-            .0
-            .par_iter()
-            .map(|(coords, _)| {
+        map_sum(&self.symbols.0, |(coords, _)| {
+            let mut adjacent_numbers: HashSet<u32> = HashSet::new();
+            for &dy in ADJACENCY_CORDS {
+                for &dx in ADJACENCY_CORDS {
+                    if dy == 0 && dx == 0 {
+                        continue;
+                    }
+                    let coords = Coords {
+                        x: ((coords.x as isize) + dx) as usize,
+                        y: ((coords.y as isize) + dy) as usize,
+                    };
+                    if let Some(&number) = self.numbers.0.get(&coords) {
+                        adjacent_numbers.insert(number);
+                    }
+                }
+            }
+            adjacent_numbers.iter().sum::<u32>()
+        })
+    }
+
+    /// Iterate over '*' symbols and find the ones that are adjacent to exactly
+    /// two numbers and multiply them, return sum of all such products
+    fn find_sum_of_gear_ratios(&self) -> u32 {
+        map_sum(&self.symbols.0, |(coords, ch)| {
+            if *ch == '*' {
                 let mut adjacent_numbers: HashSet<u32> = HashSet::new();
+
                 for &dy in ADJACENCY_CORDS {
                     for &dx in ADJACENCY_CORDS {
                         if dy == 0 && dx == 0 {
@@ -112,48 +135,18 @@ impl Grid {
                         }
                     }
                 }
-                adjacent_numbers.iter().sum::<u32>()
-            })
-            .sum()
-    }
-
-    /// Iterate over '*' symbols and find the ones that are adjacent to exactly
-    /// two numbers and multiply them, return sum of all such products
-    fn find_sum_of_gear_ratios(&self) -> u32 {
-        self.symbols
-            .0
-            .par_iter()
-            .filter_map(|(coords, ch)| {
-                if *ch == '*' {
-                    let mut adjacent_numbers: HashSet<u32> = HashSet::new();
-
-                    for &dy in ADJACENCY_CORDS {
-                        for &dx in ADJACENCY_CORDS {
-                            if dy == 0 && dx == 0 {
-                                continue;
-                            }
-                            let coords = Coords {
-                                x: ((coords.x as isize) + dx) as usize,
-                                y: ((coords.y as isize) + dy) as usize,
-                            };
-                            if let Some(&number) = self.numbers.0.get(&coords) {
-                                adjacent_numbers.insert(number);
-                            }
-                        }
-                    }
-                    if adjacent_numbers.len() == 2 {
-                        let mut adjacent_numbers = adjacent_numbers.iter();
-                        let first = adjacent_numbers.next().unwrap();
-                        let second = adjacent_numbers.next().unwrap();
-                        Some(first * second)
-                    } else {
-                        None
-                    }
+                if adjacent_numbers.len() == 2 {
+                    let mut adjacent_numbers = adjacent_numbers.iter();
+                    let first = adjacent_numbers.next().unwrap();
+                    let second = adjacent_numbers.next().unwrap();
+                    first * second
                 } else {
-                    None
+                    0
                 }
-            })
-            .sum()
+            } else {
+                0
+            }
+        })
     }
 }
 
@@ -167,6 +160,24 @@ pub fn part2(input: &str) -> u32 {
     Grid::new(input).find_sum_of_gear_ratios()
 }
 
+impl fmt::Display for Numbers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (coords, number) in &self.0 {
+            writeln!(f, "{} -> {}", coords, number)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Symbols {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (coords, ch) in &self.0 {
+            writeln!(f, "{coords} -> '{ch}'")?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{part1, part2};
@@ -211,21 +222,3 @@ mod tests {
         assert_eq!(part2(SAMPLE), 467835);
     }
 }
-
-impl fmt::Display for Numbers {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for (coords, number) in &self.0 {
-            writeln!(f, "{} -> {}", coords, number)?;
-        }
-        Ok(())
-    }
-}
-
-impl fmt::Display for Symbols {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for (coords, ch) in &self.0 {
-            writeln!(f, "{coords} -> '{ch}'")?;
-        }
-        Ok(())
-    }
-}
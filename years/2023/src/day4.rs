@@ -1,46 +1,46 @@
-use core::panic;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashSet, VecDeque};
+use std::io::{self, BufRead};
 
-use rayon::{iter::ParallelIterator, str::ParallelString};
+use aoc_common::parsing::{parse_number, ParseError};
+use aoc_common::streaming::{for_each_line, sum_lines};
 
 #[aoc(day4, part1, ParseAndStoreEverything)]
 pub fn part1(input: &str) -> u32 {
-    input
-        .par_lines()
-        .map(|line| {
-            let colon_index = line.find(':').unwrap();
-            let id_str = &line[5..colon_index];
-            let _id = id_str.trim().parse::<usize>().unwrap();
-            let lottery_str = &line[colon_index + 1..];
-
-            let parts: Vec<&str> = lottery_str.split('|').collect();
-            if parts.len() != 2 {
-                panic!("Input does not contain exactly one delimiter '|'");
-            }
-
-            let winning_numbers: HashSet<u32> = parts[0]
-                .split_whitespace()
-                .filter_map(|num| num.parse::<u32>().ok())
-                .collect();
-
-            let mut score = 0;
-
-            parts[1]
-                .split_whitespace()
-                .filter_map(|num| num.parse::<u32>().ok())
-                .for_each(|num| {
-                    if winning_numbers.contains(&num) {
-                        if score == 0 {
-                            score = 1;
-                        } else {
-                            score *= 2;
-                        }
-                    }
-                });
+    part1_stream(input.as_bytes()).unwrap()
+}
 
-            score
+pub fn part1_stream<R: BufRead>(reader: R) -> io::Result<u32> {
+    sum_lines(reader, |line| {
+        Ok(match matching_numbers(line)? {
+            0 => 0,
+            matching => 1 << (matching - 1),
         })
-        .sum()
+    })
+}
+
+/// How many of the numbers on a `Card n: winning | owned` line are winning
+fn matching_numbers(line: &str) -> Result<usize, ParseError> {
+    let (card, numbers) = line
+        .split_once(':')
+        .ok_or(ParseError::Missing("':' after card id"))?;
+    let id_str = card.get(5..).ok_or(ParseError::Missing("card id"))?;
+    let _id: usize = parse_number(id_str.trim())?;
+    let (winning_part, owned_part) = numbers
+        .split_once('|')
+        .ok_or(ParseError::Missing("'|' between winning and owned numbers"))?;
+
+    let winning_numbers: HashSet<u32> = winning_part
+        .split_whitespace()
+        .map(parse_number)
+        .collect::<Result<_, _>>()?;
+
+    let mut matching = 0;
+    for num in owned_part.split_whitespace() {
+        if winning_numbers.contains(&parse_number(num)?) {
+            matching += 1;
+        }
+    }
+    Ok(matching)
 }
 
 #[derive(Debug, Clone, Default, Copy)]
@@ -209,35 +209,33 @@ pub fn part_2_better_iterative(input: &str) -> u32 {
 
 #[aoc(day4, part2, Fast_DynamicMemoryAllocation)]
 pub fn part2_fast_dynamic_memory_allocation(input: &str) -> usize {
-    let mut increment_map: HashMap<usize, usize> = HashMap::with_capacity(200);
-    input
-        .lines()
-        .enumerate()
-        .map(|(i, line)| {
-            let colon_index = line.find(':').unwrap();
-            let (winning_part, matching_part) = line[colon_index + 1..].split_once('|').unwrap();
+    part2_stream(input.as_bytes()).unwrap()
+}
 
-            let winning_numbers: Vec<usize> = winning_part
-                .split_whitespace()
-                .filter_map(|num| num.parse().ok())
-                .collect();
+/// Only keeps the copies won for the next few cards, a card can never win
+/// more cards ahead than it has numbers
+pub fn part2_stream<R: BufRead>(reader: R) -> io::Result<usize> {
+    let mut pending: VecDeque<usize> = VecDeque::new();
+    let mut total = 0;
 
-            let matching_numbers = matching_part
-                .split_whitespace()
-                .filter_map(|num| num.parse().ok())
-                .filter(|num| winning_numbers.contains(num))
-                .count();
+    for_each_line(reader, |line| {
+        let matching_numbers = matching_numbers(line)?;
 
-            let current_card_copies = 1 + increment_map.get(&i).unwrap_or(&0);
+        let current_card_copies = 1 + pending.pop_front().unwrap_or(0);
 
-            // Apply future winnings
-            for j in 1..=matching_numbers {
-                *increment_map.entry(i + j).or_insert(0) += current_card_copies;
-            }
+        if pending.len() < matching_numbers {
+            pending.resize(matching_numbers, 0);
+        }
+        pending
+            .iter_mut()
+            .take(matching_numbers)
+            .for_each(|copies| *copies += current_card_copies);
 
-            current_card_copies
-        })
-        .sum()
+        total += current_card_copies;
+        Ok(())
+    })?;
+
+    Ok(total)
 }
 
 #[cfg(test)]
@@ -290,9 +288,13 @@ pub fn part2_no_dynamic_memory_allocation(input: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::{
-        part1, part2_fast_dynamic_memory_allocation, part2_iterative,
-        part2_no_dynamic_memory_allocation, part2_recurrence, part_2_better_iterative,
+        part1, part1_stream, part2_fast_dynamic_memory_allocation, part2_iterative,
+        part2_no_dynamic_memory_allocation, part2_recurrence, part2_stream,
+        part_2_better_iterative, INC_ARRAY_SIZE, WIN_ARRAY_SIZE,
     };
+    use crate::generators;
+    use proptest::prelude::*;
+    use std::io::{self, BufReader};
 
     static SAMPLE: &str = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
@@ -330,4 +332,46 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
     fn test_part2_no_dynamic_memory_allocation() {
         assert_eq!(part2_no_dynamic_memory_allocation(SAMPLE), 30);
     }
+
+    #[test]
+    fn test_part2_stream() {
+        let reader = BufReader::with_capacity(3, SAMPLE.as_bytes());
+        assert_eq!(part2_stream(reader).unwrap(), 30);
+    }
+
+    #[test]
+    fn test_malformed_card_is_invalid_data() {
+        for card in [
+            "Card 1 41 48 | 83 86",
+            "Card x: 41 48 | 83 86",
+            "Card 1: 41 48 83 86",
+            "Card 1: 41 48 | 83 8x",
+        ] {
+            let input = format!("{}\n{card}", SAMPLE.lines().next().unwrap());
+            let err = part2_stream(input.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{card}");
+            assert_eq!(
+                part1_stream(input.as_bytes()).unwrap_err().kind(),
+                io::ErrorKind::InvalidData
+            );
+        }
+    }
+
+    proptest! {
+        // Bounded by the array sizes the no-allocation variant is compiled with in tests
+        #[test]
+        fn test_part_2_variants_agree(
+            seed: u64,
+            cards in 1..=INC_ARRAY_SIZE,
+            winning in 1..=WIN_ARRAY_SIZE,
+            owned in 1usize..25,
+        ) {
+            let input = generators::scratchcards(seed, cards, winning, owned);
+            let expected = part2_recurrence(&input);
+            prop_assert_eq!(part2_iterative(&input), expected);
+            prop_assert_eq!(part_2_better_iterative(&input), expected);
+            prop_assert_eq!(part2_fast_dynamic_memory_allocation(&input) as u32, expected);
+            prop_assert_eq!(part2_no_dynamic_memory_allocation(&input) as u32, expected);
+        }
+    }
 }
@@ -1,22 +1,22 @@
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use std::ops::Range;
 use std::str::FromStr;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, Mutex};
+
+use aoc_common::execution::map_reduce;
+use aoc_common::parsing::{parse_number, ParseError};
 
 #[derive(Debug)]
 struct SeedsToPlant(Vec<u64>);
 
 impl FromStr for SeedsToPlant {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, ()> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         Ok(SeedsToPlant(
             s.split(':')
                 .nth(1)
-                .unwrap()
+                .ok_or(ParseError::Missing("':' after seeds"))?
                 .split_whitespace()
-                .map(|s| s.parse().unwrap())
-                .collect(),
+                .map(parse_number)
+                .collect::<Result<_, _>>()?,
         ))
     }
 }
@@ -37,33 +37,42 @@ struct Mapping {
 }
 
 impl FromStr for Mapping {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, ()> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         let parts: Vec<u64> = s
             .split_whitespace()
-            .map(|num| num.parse().unwrap())
-            .collect();
+            .map(parse_number)
+            .collect::<Result<_, _>>()?;
+        let [destination, source, length] = parts[..] else {
+            return Err(ParseError::Missing("destination, source and length"));
+        };
         Ok(Mapping {
-            destination: (parts[0]..(parts[0] + parts[2])),
-            source: (parts[1]..(parts[1] + parts[2])),
+            destination: (destination
+                ..destination
+                    .checked_add(length)
+                    .ok_or(ParseError::Overflow("destination range"))?),
+            source: (source
+                ..source
+                    .checked_add(length)
+                    .ok_or(ParseError::Overflow("source range"))?),
         })
     }
 }
 
 #[derive(Debug)]
-struct Map {
+pub(crate) struct Map {
     mappings: Vec<Mapping>,
 }
 
 impl FromStr for Map {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, ()> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         Ok(Map {
             mappings: s
                 .lines()
                 .skip(1)
-                .map(|line| line.parse().unwrap())
-                .collect(),
+                .map(|line| line.parse())
+                .collect::<Result<_, _>>()?,
         })
     }
 }
@@ -122,12 +131,12 @@ pub fn part_2_brute_force(input: &str) -> u64 {
     seeds
         .iter()
         .map(|range| {
-            range
-                .clone()
-                .into_par_iter()
-                .map(|seed| maps.iter().fold(seed, |output, map| map.map_output(output)))
-                .min()
-                .unwrap()
+            map_reduce(
+                range.clone(),
+                |seed| maps.iter().fold(seed, |output, map| map.map_output(output)),
+                || u64::MAX,
+                u64::min,
+            )
         })
         .min()
         .unwrap()
@@ -144,32 +153,31 @@ pub fn part_2_brute_force_2(input: &str) -> u64 {
     let mut maps: Vec<Map> = sections.map(|section| section.parse().unwrap()).collect();
     maps.reverse();
 
-    let result = Arc::new(AtomicU64::new(u64::MAX));
-    let found = Arc::new(Mutex::new(false));
-
-    (0u64..0xFFFF).into_par_iter().for_each_with(
-        (result.clone(), found.clone()),
-        |(result, found), location| {
+    // Keep the lowest matching location rather than whichever thread finds
+    // one first, so every execution mode agrees
+    map_reduce(
+        0u64..0xFFFF,
+        |location| {
             let seed = maps
                 .iter()
                 .fold(location, |input, map| map.reverse_map_output(input));
 
             if seeds.iter().any(|range| range.contains(&seed)) {
-                let mut found = found.lock().unwrap();
-                if !*found {
-                    *found = true;
-                    result.store(location, Ordering::SeqCst);
-                }
+                location
+            } else {
+                u64::MAX
             }
         },
-    );
-
-    result.load(Ordering::SeqCst)
+        || u64::MAX,
+        u64::min,
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generators;
+    use proptest::prelude::*;
 
     static SAMPLE: &str = r#"seeds: 79 14 55 13
 
@@ -219,4 +227,32 @@ humidity-to-location map:
     fn test_part_2_brute_force_2() {
         assert_eq!(part_2_brute_force_2(SAMPLE), 46);
     }
+
+    #[test]
+    fn test_short_line_is_an_error() {
+        assert_eq!(
+            Map::from_str("seed-to-soil map:\n50 98").unwrap_err(),
+            ParseError::Missing("destination, source and length")
+        );
+        assert_eq!(
+            Map::from_str("seed-to-soil map:\n1 18446744073709551615 2").unwrap_err(),
+            ParseError::Overflow("source range")
+        );
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        // BruteForce2 only scans locations below 0xFFFF
+        #[test]
+        fn test_part_2_variants_agree(
+            seed: u64,
+            seed_ranges in 1usize..4,
+            blocks in 1usize..8,
+            max_value in 8u64..0xFFFF,
+        ) {
+            let input = generators::almanac(seed, seed_ranges, blocks, max_value);
+            prop_assert_eq!(part_2_brute_force_2(&input), part_2_brute_force(&input));
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use aoc_common::execution::map_sum;
+use aoc_common::parsing::{parse_number, ParseError};
 
 // th = time spent holding button (ms)
 // tr = total race time (ms)
@@ -10,7 +11,7 @@ use rayon::iter::{IntoParallelIterator, ParallelIterator};
 // d = th * (tr - th)
 
 #[derive(Debug)]
-struct Race {
+pub(crate) struct Race {
     total_race_time_ms: u64,
     record_distance_mm: u64,
 }
@@ -23,33 +24,19 @@ impl Race {
     }
 
     pub fn calculate_number_of_ways_to_beat_the_record_parallel(&self) -> u64 {
-        (1..self.total_race_time_ms)
-            .into_par_iter()
-            .filter_map(|th| {
-                let distance = self.calculate_distance(th);
-                if distance > self.record_distance_mm {
-                    Some(1)
-                } else {
-                    None
-                }
-            })
-            .count() as u64
+        map_sum(1..self.total_race_time_ms, |th| {
+            let distance = self.calculate_distance(th);
+            (distance > self.record_distance_mm) as u64
+        })
     }
 
     pub fn calculate_number_of_ways_to_beat_the_record_parallel_midpoint(&self) -> u64 {
         let midpoint = self.total_race_time_ms / 2;
 
-        let count_up_to_midpoint = (1..=midpoint)
-            .into_par_iter()
-            .filter_map(|th| {
-                let distance = self.calculate_distance(th);
-                if distance > self.record_distance_mm {
-                    Some(1)
-                } else {
-                    None
-                }
-            })
-            .count() as u64;
+        let count_up_to_midpoint = map_sum(1..=midpoint, |th| {
+            let distance = self.calculate_distance(th);
+            (distance > self.record_distance_mm) as u64
+        });
 
         if self.total_race_time_ms % 2 == 0 {
             2 * count_up_to_midpoint - 1
@@ -68,15 +55,24 @@ impl Race {
         let c = -(self.record_distance_mm as i64);
 
         let discriminant = b.pow(2) - 4 * a * c;
-        if discriminant < 0 {
+        if discriminant <= 0 {
             return 0;
         }
 
-        let sqrt_discriminant = (discriminant as f64).sqrt() as i64;
-        let root1 = (-b - sqrt_discriminant) / (2 * a);
-        let root2 = (-b + sqrt_discriminant) / (2 * a);
+        // Work with the exact integer square root instead of f64 and nudge the
+        // lower root up to the first hold time that actually beats the record,
+        // the winning hold times are symmetric around tr / 2
+        let sqrt_discriminant = discriminant.isqrt();
+        let mut lowest = (b - sqrt_discriminant) / 2;
+        while lowest * 2 <= b && self.calculate_distance(lowest as u64) <= self.record_distance_mm {
+            lowest += 1;
+        }
+
+        if lowest * 2 > b {
+            return 0;
+        }
 
-        (root1 - root2) as u64
+        (b - 2 * lowest + 1) as u64
     }
 
     fn calculate_distance(&self, th: u64) -> u64 {
@@ -84,13 +80,20 @@ impl Race {
     }
 }
 
+/// Numbers of a `Time:` or `Distance:` line, past its label
+fn record_values<'a>(line: Option<&'a str>, label: &'static str) -> Result<&'a str, ParseError> {
+    line.and_then(|line| line.get(11..))
+        .ok_or(ParseError::Missing(label))
+}
+
 impl FromStr for Race {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, ()> {
-        let lines: Vec<&str> = s.lines().collect();
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut lines = s.lines();
 
-        let time = lines[0][11..].replace(' ', "").parse::<u64>().unwrap();
-        let distance = lines[1][11..].replace(' ', "").parse::<u64>().unwrap();
+        let time = parse_number(&record_values(lines.next(), "race times")?.replace(' ', ""))?;
+        let distance =
+            parse_number(&record_values(lines.next(), "record distances")?.replace(' ', ""))?;
 
         Ok(Race {
             total_race_time_ms: time,
@@ -111,19 +114,19 @@ impl Races {
 }
 
 impl FromStr for Races {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, ()> {
-        let lines: Vec<&str> = s.lines().collect();
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut lines = s.lines();
 
-        let times: Vec<u64> = lines[0][11..]
+        let times: Vec<u64> = record_values(lines.next(), "race times")?
             .split_whitespace()
-            .map(|num| num.parse::<u64>().unwrap())
-            .collect();
+            .map(parse_number)
+            .collect::<Result<_, _>>()?;
 
-        let distances: Vec<u64> = lines[1][11..]
+        let distances: Vec<u64> = record_values(lines.next(), "record distances")?
             .split_whitespace()
-            .map(|num| num.parse::<u64>().unwrap())
-            .collect();
+            .map(parse_number)
+            .collect::<Result<_, _>>()?;
 
         let races = times
             .into_iter()
@@ -165,6 +168,7 @@ pub fn part_2_equation(input: &str) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     static SAMPLE: &str = r#"Time:      7  15   30
 Distance:  9  40  200
@@ -189,4 +193,40 @@ Distance:  9  40  200
     fn test_part_2_equation() {
         assert_eq!(part_2_equation(SAMPLE), 71503);
     }
+
+    #[test]
+    fn test_short_line_is_an_error() {
+        assert_eq!(
+            Race::from_str("Time: 7").unwrap_err(),
+            ParseError::Missing("race times")
+        );
+        assert_eq!(
+            Race::from_str("Time:        7  15\nDistance:").unwrap_err(),
+            ParseError::Missing("record distances")
+        );
+        assert_eq!(
+            Race::from_str("Time:        7  1x\nDistance:   9  40").unwrap_err(),
+            ParseError::InvalidNumber("71x".to_string())
+        );
+    }
+
+    fn beatable_race() -> impl Strategy<Value = Race> {
+        (2u64..100_000).prop_flat_map(|time| {
+            let best = (time / 2) * (time - time / 2);
+            (0..best).prop_map(move |record| Race {
+                total_race_time_ms: time,
+                record_distance_mm: record,
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_part_2_variants_agree(race in beatable_race()) {
+            let expected = race.calculate_number_of_ways_to_beat_the_record();
+            prop_assert_eq!(race.calculate_number_of_ways_to_beat_the_record_parallel(), expected);
+            prop_assert_eq!(race.calculate_number_of_ways_to_beat_the_record_parallel_midpoint(), expected);
+            prop_assert_eq!(race.calculate_number_of_ways_to_beat_the_record_equation(), expected);
+        }
+    }
 }
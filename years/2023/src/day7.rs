@@ -1,5 +1,12 @@
 use core::{fmt, panic};
-use std::{cmp::Ordering, collections::BTreeSet};
+use std::{
+    cmp::Ordering,
+    collections::BTreeSet,
+    io::{self, BufRead},
+};
+
+use aoc_common::parsing::{parse_number, ParseError};
+use aoc_common::streaming::for_each_line;
 
 #[derive(Debug, PartialEq, PartialOrd, Ord, Eq, Copy, Clone)]
 enum HandType {
@@ -15,11 +22,15 @@ enum HandType {
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 struct Cards([u8; 5]);
 
-impl From<(&str, bool)> for Cards {
-    fn from((line, use_wildcard): (&str, bool)) -> Self {
+impl TryFrom<(&str, bool)> for Cards {
+    type Error = ParseError;
+
+    fn try_from((line, use_wildcard): (&str, bool)) -> Result<Self, Self::Error> {
         let mut cards = Cards([0; 5]);
-        line.chars().take(5).enumerate().for_each(|(index, ch)| {
-            cards.0[index] = if use_wildcard {
+        let mut faces = line.chars();
+        for card in cards.0.iter_mut() {
+            let ch = faces.next().ok_or(ParseError::Missing("five cards"))?;
+            *card = if use_wildcard {
                 match ch {
                     'J' => 0,
                     '2' => 1,
@@ -34,7 +45,7 @@ impl From<(&str, bool)> for Cards {
                     'Q' => 10,
                     'K' => 11,
                     'A' => 12,
-                    _ => panic!("Invalid face"),
+                    _ => return Err(ParseError::InvalidChar(ch)),
                 }
             } else {
                 match ch {
@@ -51,37 +62,39 @@ impl From<(&str, bool)> for Cards {
                     'Q' => 10,
                     'K' => 11,
                     'A' => 12,
-                    _ => panic!("Invalid face"),
+                    _ => return Err(ParseError::InvalidChar(ch)),
                 }
             };
-        });
-        cards
+        }
+        Ok(cards)
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
-struct Hand {
+pub(crate) struct Hand {
     cards: Cards,
     hand_type: HandType,
     bid: u64,
 }
 
-impl From<(&str, bool)> for Hand {
-    fn from(s: (&str, bool)) -> Self {
-        let cards = Cards::from((s.0, s.1));
+impl TryFrom<(&str, bool)> for Hand {
+    type Error = ParseError;
+
+    fn try_from(s: (&str, bool)) -> Result<Self, Self::Error> {
+        let cards = Cards::try_from((s.0, s.1))?;
         let hand_type = if s.1 {
             determine_hand_type_with_joker(&cards)
         } else {
             determine_hand_type(&cards)
         };
 
-        let bid = s.0[6..].parse::<u64>().unwrap();
+        let bid = parse_number(s.0.get(6..).ok_or(ParseError::Missing("bid"))?)?;
 
-        Hand {
+        Ok(Hand {
             cards,
             hand_type,
             bid,
-        }
+        })
     }
 }
 
@@ -186,49 +199,37 @@ fn determine_hand_type_with_joker(cards: &Cards) -> HandType {
 
 #[aoc(day7, part1)]
 pub fn part_1(input: &str) -> u64 {
-    input
-        .lines()
-        .map(|hand_str| Hand::from((hand_str, false)))
-        .collect::<BTreeSet<Hand>>()
-        .iter()
-        .rev()
-        .enumerate()
-        .map(|(index, hand)| hand.bid * (index as u64 + 1))
-        .sum()
+    part_1_stream(input.as_bytes()).unwrap()
 }
 
 #[aoc(day7, part2)]
 pub fn part_2(input: &str) -> u64 {
-    input
-        .lines()
-        .map(|hand_str| Hand::from((hand_str, true)))
-        .collect::<BTreeSet<Hand>>()
-        .iter()
-        .rev()
-        .enumerate()
-        .map(|(index, hand)| hand.bid * (index as u64 + 1))
-        .sum()
+    part_2_stream(input.as_bytes()).unwrap()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub fn part_1_stream<R: BufRead>(reader: R) -> io::Result<u64> {
+    total_winnings(reader, false)
+}
 
-    static SAMPLE: &str = r#"32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483"#;
+pub fn part_2_stream<R: BufRead>(reader: R) -> io::Result<u64> {
+    total_winnings(reader, true)
+}
 
-    #[test]
-    fn test_part_1() {
-        assert_eq!(part_1(SAMPLE), 6440);
-    }
+/// Equal hands collapse in the set, so memory is bounded by the 13^5 possible
+/// hands no matter how long the input is
+fn total_winnings<R: BufRead>(reader: R, use_wildcard: bool) -> io::Result<u64> {
+    let mut hands = BTreeSet::new();
+    for_each_line(reader, |hand_str| {
+        hands.insert(Hand::try_from((hand_str, use_wildcard))?);
+        Ok(())
+    })?;
 
-    #[test]
-    fn test_part_2() {
-        assert_eq!(part_2(SAMPLE), 5905);
-    }
+    Ok(hands
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, hand)| hand.bid * (index as u64 + 1))
+        .sum())
 }
 
 impl fmt::Display for Cards {
@@ -259,3 +260,47 @@ impl fmt::Display for Hand {
         write!(f, "{} | {} | {}", self.cards, self.bid, self.hand_type)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    static SAMPLE: &str = r#"32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483"#;
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(SAMPLE), 6440);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(SAMPLE), 5905);
+    }
+
+    #[test]
+    fn test_part_2_stream() {
+        let reader = BufReader::with_capacity(4, SAMPLE.as_bytes());
+        assert_eq!(part_2_stream(reader).unwrap(), 5905);
+    }
+
+    #[test]
+    fn test_short_line_is_an_error() {
+        assert_eq!(
+            Hand::try_from(("32T3K", false)).unwrap_err(),
+            ParseError::Missing("bid")
+        );
+        assert_eq!(
+            Hand::try_from(("32T", true)).unwrap_err(),
+            ParseError::Missing("five cards")
+        );
+        assert_eq!(
+            Hand::try_from(("32T3K 7x5", false)).unwrap_err(),
+            ParseError::InvalidNumber("7x5".to_string())
+        );
+    }
+}
@@ -1,10 +1,11 @@
-use rayon::prelude::*;
 use std::{collections::HashMap, str::FromStr};
 
-use crate::helpers::lcm;
+use aoc_common::execution::map_reduce;
+use aoc_common::helpers::lcm;
+use aoc_common::parsing::ParseError;
 
 #[derive(Debug)]
-struct Map {
+pub(crate) struct Map {
     directions: Vec<usize>,
     nodes: Vec<[usize; 2]>,
     start_indices: Vec<usize>,
@@ -34,22 +35,26 @@ impl Map {
 }
 
 impl FromStr for Map {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
 
         let directions = lines
             .next()
-            .unwrap()
+            .ok_or(ParseError::Missing("directions"))?
             .chars()
             .map(|d| (d == 'R') as usize)
             .collect();
 
         let mut node_names: Vec<&str> = lines
             .skip(1)
-            .map(|l| l.split_once(" =").unwrap().0)
-            .collect();
+            .map(|l| {
+                l.split_once(" =")
+                    .map(|(name, _)| name)
+                    .ok_or(ParseError::Missing("' =' after node name"))
+            })
+            .collect::<Result<_, _>>()?;
 
         node_names.sort_unstable();
 
@@ -68,11 +73,22 @@ impl FromStr for Map {
 
         let mut nodes = vec![[0, 0]; node_names.len()];
 
+        let index = |name: &str| {
+            idx_by_node
+                .get(name)
+                .copied()
+                .ok_or_else(|| ParseError::UnknownNode(name.to_string()))
+        };
+
         for l in s.lines().skip(2) {
-            let (from, rest) = l.split_once(" = (").unwrap();
-            let (l, rr) = rest.split_once(", ").unwrap();
-            let r = &rr[0..3];
-            nodes[idx_by_node[from]] = [idx_by_node[l], idx_by_node[r]];
+            let (from, rest) = l
+                .split_once(" = (")
+                .ok_or(ParseError::Missing("' = (' after node name"))?;
+            let (l, rr) = rest
+                .split_once(", ")
+                .ok_or(ParseError::Missing("', ' between nodes"))?;
+            let r = rr.get(0..3).ok_or(ParseError::Missing("right node"))?;
+            nodes[index(from)?] = [index(l)?, index(r)?];
         }
 
         Ok(Map {
@@ -84,21 +100,27 @@ impl FromStr for Map {
     }
 }
 
+pub fn parse(input: &str) -> Result<Map, ParseError> {
+    Map::from_str(input)
+}
+
 #[aoc(day8, part1)]
-fn camels_map(input: &str) -> usize {
-    let map = Map::from_str(input).unwrap();
+pub fn camels_map(input: &str) -> usize {
+    let map = parse(input).unwrap();
 
     map.instructions(0, Some(map.nodes.len() - 1))
 }
 
 #[aoc(day8, part2)]
-fn ghosts_map(input: &str) -> usize {
-    let map = Map::from_str(input).unwrap();
-
-    map.start_indices
-        .par_iter()
-        .map(|&start| map.instructions(start, None))
-        .reduce(|| 1, lcm)
+pub fn ghosts_map(input: &str) -> usize {
+    let map = parse(input).unwrap();
+
+    map_reduce(
+        &map.start_indices,
+        |&start| map.instructions(start, None),
+        || 1,
+        lcm,
+    )
 }
 
 #[cfg(test)]
@@ -146,4 +168,16 @@ XXX = (XXX, XXX)"#;
     fn test_ghosts_map() {
         assert_eq!(ghosts_map(SAMPLE_3), 6);
     }
+
+    #[test]
+    fn test_short_line_is_an_error() {
+        assert_eq!(
+            Map::from_str("LR\n\nAAA = (BBB, C").unwrap_err(),
+            ParseError::Missing("right node")
+        );
+        assert_eq!(
+            Map::from_str("LR\n\nAAA = (BBB, CCC)").unwrap_err(),
+            ParseError::UnknownNode("BBB".to_string())
+        );
+    }
 }
@@ -0,0 +1,85 @@
+use smallvec::SmallVec;
+use std::io::{self, BufRead};
+
+use aoc_common::helpers::extrapolate as next_value;
+use aoc_common::parsing::parse_number;
+use aoc_common::streaming::sum_lines;
+
+#[aoc(day9, part1)]
+pub fn extrapolate(input: &str) -> i64 {
+    extrapolate_stream(input.as_bytes()).unwrap()
+}
+
+pub fn extrapolate_stream<R: BufRead>(reader: R) -> io::Result<i64> {
+    sum_lines(reader, |line| {
+        let mut nums: Vec<i64> = line
+            .split_ascii_whitespace()
+            .map(parse_number)
+            .collect::<Result<_, _>>()?;
+
+        Ok(next_value(&mut nums))
+    })
+}
+
+#[aoc(day9, part2)]
+pub fn extrapolate_backwards(input: &str) -> i64 {
+    extrapolate_backwards_stream(input.as_bytes()).unwrap()
+}
+
+pub fn extrapolate_backwards_stream<R: BufRead>(reader: R) -> io::Result<i64> {
+    sum_lines(reader, |line| {
+        let mut nums: SmallVec<[i64; 20]> = line
+            .split_ascii_whitespace()
+            .map(parse_number)
+            .collect::<Result<_, _>>()?;
+
+        nums.reverse();
+
+        Ok(next_value(&mut nums))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    static SAMPLE: &str = r#"0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45"#;
+
+    static SAMPLE_1: &str = r#"0 3 6 9 12 15"#;
+    static SAMPLE_2: &str = r#"1 3 6 10 15 21"#;
+    static SAMPLE_3: &str = r#"10 13 16 21 30 45"#;
+
+    #[test]
+    fn test_extrapolate() {
+        assert_eq!(extrapolate(SAMPLE), 114);
+    }
+
+    #[test]
+    fn test_extrapolate_1() {
+        assert_eq!(extrapolate_backwards(SAMPLE_1), -3);
+    }
+
+    #[test]
+    fn test_extrapolate_2() {
+        assert_eq!(extrapolate_backwards(SAMPLE_2), 0);
+    }
+
+    #[test]
+    fn test_extrapolate_3() {
+        assert_eq!(extrapolate_backwards(SAMPLE_3), 5);
+    }
+
+    #[test]
+    fn test_extrapolate_backwards() {
+        assert_eq!(extrapolate_backwards(SAMPLE), 2);
+    }
+
+    #[test]
+    fn test_extrapolate_stream() {
+        let reader = BufReader::with_capacity(5, SAMPLE.as_bytes());
+        assert_eq!(extrapolate_stream(reader).unwrap(), 114);
+    }
+}
@@ -0,0 +1,30 @@
+//! Entry points for the `fuzz/` targets. The parsers stay private to their
+//! days, these only report whether the input parsed.
+
+use std::str::FromStr;
+
+pub use aoc_common::parsing::ParseError;
+
+pub fn day2_game_data(input: &str) -> Result<(), ParseError> {
+    crate::day2::GameData::try_from(input).map(drop)
+}
+
+pub fn day3_grid(input: &str) {
+    crate::day3::Grid::new(input);
+}
+
+pub fn day5_map(input: &str) -> Result<(), ParseError> {
+    crate::day5::Map::from_str(input).map(drop)
+}
+
+pub fn day6_race(input: &str) -> Result<(), ParseError> {
+    crate::day6::Race::from_str(input).map(drop)
+}
+
+pub fn day7_hand(input: &str, use_wildcard: bool) -> Result<(), ParseError> {
+    crate::day7::Hand::try_from((input, use_wildcard)).map(drop)
+}
+
+pub fn day8_map(input: &str) -> Result<(), ParseError> {
+    crate::day8::Map::from_str(input).map(drop)
+}
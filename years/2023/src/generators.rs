@@ -0,0 +1,477 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+/// Small SplitMix64 generator, so that the same seed produces the same input
+/// on every platform and regardless of dependency versions.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..n`, `n` must be non-zero
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        range.start() + self.below(range.end() - range.start() + 1)
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// True with probability `numerator / denominator`
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+const DIGIT_WORDS: &[&str] = &[
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+const SYMBOLS: &[u8] = b"*#+$@/=%&-";
+
+const FACES: &[u8] = b"23456789TJQKA";
+
+const ALMANAC_MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+fn push_lowercase(rng: &mut Rng, out: &mut String, len: usize) {
+    (0..len).for_each(|_| out.push((b'a' + rng.below(26) as u8) as char));
+}
+
+/// Day 1: calibration lines mixing filler letters, digits and digit words.
+/// Every line contains at least one numeric digit so part 1 stays solvable.
+pub fn calibration_document(seed: u64, lines: usize, max_filler: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut out = String::new();
+
+    for _ in 0..lines {
+        let tokens = rng.range(1..=5);
+        let digit_at = rng.below(tokens);
+        for token in 0..tokens {
+            let filler = rng.index(max_filler + 1);
+            push_lowercase(&mut rng, &mut out, filler);
+            if token == digit_at || rng.chance(1, 3) {
+                out.push((b'1' + rng.below(9) as u8) as char);
+            } else {
+                let word = *rng.pick(DIGIT_WORDS);
+                out.push_str(word);
+            }
+        }
+        let filler = rng.index(max_filler + 1);
+        push_lowercase(&mut rng, &mut out, filler);
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Day 2: games with up to `max_subsets` draws of at most `max_cubes` cubes per colour.
+pub fn cube_games(seed: u64, games: usize, max_subsets: usize, max_cubes: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut out = String::new();
+
+    for id in 1..=games {
+        write!(out, "Game {id}:").unwrap();
+        let subsets = rng.range(1..=max_subsets as u64);
+        for subset in 0..subsets {
+            let mut colors = ["red", "green", "blue"];
+            rng.shuffle(&mut colors);
+            let count = rng.range(1..=3) as usize;
+            for (index, color) in colors[..count].iter().enumerate() {
+                let separator = if index == 0 { "" } else { "," };
+                let cubes = rng.range(1..=max_cubes as u64);
+                write!(out, "{separator} {cubes} {color}").unwrap();
+            }
+            if subset + 1 < subsets {
+                out.push(';');
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Day 3: `width` x `height` schematic with numbers of up to three digits and symbols.
+pub fn engine_schematic(seed: u64, width: usize, height: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut out = String::new();
+
+    for _ in 0..height {
+        let mut x = 0;
+        while x < width {
+            let roll = rng.below(10);
+            let digits = rng.range(1..=3) as usize;
+            if roll < 3 && x + digits <= width {
+                out.push((b'1' + rng.below(9) as u8) as char);
+                (1..digits).for_each(|_| out.push((b'0' + rng.below(10) as u8) as char));
+                x += digits;
+                // Keep neighbouring numbers apart so they are not read as one
+                if x < width {
+                    out.push(if rng.chance(1, 4) {
+                        *rng.pick(SYMBOLS) as char
+                    } else {
+                        '.'
+                    });
+                    x += 1;
+                }
+            } else if roll < 4 {
+                out.push(*rng.pick(SYMBOLS) as char);
+                x += 1;
+            } else {
+                out.push('.');
+                x += 1;
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Day 4: scratchcards numbered from 1 with distinct numbers in `1..=99`.
+/// A card never wins copies of cards past the end of the table.
+pub fn scratchcards(seed: u64, cards: usize, winning: usize, owned: usize) -> String {
+    assert!(winning + owned <= 99, "not enough distinct numbers");
+
+    let mut rng = Rng::new(seed);
+    let mut out = String::new();
+    let id_width = cards.to_string().len();
+
+    for id in 1..=cards {
+        let mut numbers: Vec<u32> = (1..=99).collect();
+        rng.shuffle(&mut numbers);
+
+        let max_matches = winning.min(owned).min(cards - id);
+        let matches = rng.index(max_matches + 1);
+
+        let winning_numbers = &numbers[..winning];
+        let mut owned_numbers: Vec<u32> = winning_numbers[..matches]
+            .iter()
+            .chain(&numbers[winning..winning + owned - matches])
+            .copied()
+            .collect();
+        rng.shuffle(&mut owned_numbers);
+
+        write!(out, "Card {id:>id_width$}:").unwrap();
+        winning_numbers
+            .iter()
+            .for_each(|n| write!(out, " {n:>2}").unwrap());
+        out.push_str(" |");
+        owned_numbers
+            .iter()
+            .for_each(|n| write!(out, " {n:>2}").unwrap());
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Day 5: almanac where every map is a bijection on `0..max_value` made of
+/// `blocks` contiguous ranges, so neither sources nor destinations overlap.
+pub fn almanac(seed: u64, seed_ranges: usize, blocks: usize, max_value: u64) -> String {
+    assert!(blocks >= 1 && blocks as u64 <= max_value);
+
+    let mut rng = Rng::new(seed);
+    let mut out = String::from("seeds:");
+
+    for _ in 0..seed_ranges {
+        let start = rng.below(max_value);
+        let length = rng.range(1..=max_value - start);
+        write!(out, " {start} {length}").unwrap();
+    }
+    out.push('\n');
+
+    for name in ALMANAC_MAPS {
+        let mut cuts: HashSet<u64> = HashSet::new();
+        while cuts.len() < blocks - 1 {
+            cuts.insert(rng.range(1..=max_value - 1));
+        }
+        let mut cuts: Vec<u64> = cuts.into_iter().collect();
+        cuts.push(0);
+        cuts.push(max_value);
+        cuts.sort_unstable();
+
+        let sources: Vec<(u64, u64)> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+        let mut order: Vec<usize> = (0..sources.len()).collect();
+        rng.shuffle(&mut order);
+
+        let mut mappings = Vec::new();
+        let mut destination = 0;
+        for index in order {
+            let (source, length) = sources[index];
+            if source != destination {
+                mappings.push((destination, source, length));
+            }
+            destination += length;
+        }
+        rng.shuffle(&mut mappings);
+
+        write!(out, "\n{name} map:\n").unwrap();
+        for (destination, source, length) in mappings {
+            writeln!(out, "{destination} {source} {length}").unwrap();
+        }
+    }
+
+    out
+}
+
+/// Day 6: races whose records can always be beaten at least once.
+/// Keep `races * digits(max_time)` small enough for the part 2 kerning to fit in `u64`.
+pub fn races(seed: u64, races: usize, max_time: u64) -> String {
+    assert!(max_time >= 2);
+
+    let mut rng = Rng::new(seed);
+    let pairs: Vec<(u64, u64)> = (0..races)
+        .map(|_| {
+            let time = rng.range(2..=max_time);
+            let best = (time / 2) * (time - time / 2);
+            (time, rng.below(best))
+        })
+        .collect();
+
+    let mut time_line = String::from("Time:     ");
+    let mut distance_line = String::from("Distance: ");
+    for (time, distance) in pairs {
+        let width = time.to_string().len().max(distance.to_string().len()) + 2;
+        write!(time_line, " {time:>width$}").unwrap();
+        write!(distance_line, " {distance:>width$}").unwrap();
+    }
+
+    format!("{time_line}\n{distance_line}\n")
+}
+
+/// Day 7: distinct hands built from a few faces each, so every hand type shows up.
+pub fn camel_hands(seed: u64, hands: usize, max_bid: u64) -> String {
+    assert!(hands <= FACES.len().pow(5));
+
+    let mut rng = Rng::new(seed);
+    let mut seen = HashSet::new();
+    let mut out = String::new();
+
+    while seen.len() < hands {
+        let mut faces = FACES.to_vec();
+        rng.shuffle(&mut faces);
+        let distinct = rng.range(1..=5) as usize;
+        let hand: String = (0..5)
+            .map(|_| *rng.pick(&faces[..distinct]) as char)
+            .collect();
+
+        if seen.insert(hand.clone()) {
+            writeln!(out, "{hand} {}", rng.range(1..=max_bid)).unwrap();
+        }
+    }
+
+    out
+}
+
+fn unique_name(rng: &mut Rng, used: &mut HashSet<String>, last: RangeInclusive<u8>) -> String {
+    loop {
+        let name: String = [
+            b'A' + rng.below(26) as u8,
+            b'A' + rng.below(26) as u8,
+            rng.range(*last.start() as u64..=*last.end() as u64) as u8,
+        ]
+        .iter()
+        .map(|&b| b as char)
+        .collect();
+
+        if used.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// Day 8: network of `ghosts` start nodes, starting with `AAA` -> `ZZZ`.
+/// Each ghost reaches its `..Z` node after a whole number of laps (at most
+/// `max_laps`) over the directions and then loops back with the same period,
+/// which is what the LCM shortcut relies on.
+pub fn node_network(seed: u64, directions: usize, ghosts: usize, max_laps: usize) -> String {
+    assert!(directions >= 1 && ghosts >= 1 && max_laps >= 1);
+
+    let mut rng = Rng::new(seed);
+    let turns: Vec<usize> = (0..directions).map(|_| rng.index(2)).collect();
+    let mut used = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+
+    let paths: Vec<Vec<String>> = (0..ghosts)
+        .map(|ghost| {
+            let length = rng.range(1..=max_laps as u64) as usize * directions;
+            let mut path = Vec::with_capacity(length + 1);
+            path.push(if ghost == 0 {
+                "AAA".to_string()
+            } else {
+                unique_name(&mut rng, &mut used, b'A'..=b'A')
+            });
+            (1..length).for_each(|_| path.push(unique_name(&mut rng, &mut used, b'B'..=b'Y')));
+            path.push(if ghost == 0 {
+                "ZZZ".to_string()
+            } else {
+                unique_name(&mut rng, &mut used, b'Z'..=b'Z')
+            });
+            path
+        })
+        .collect();
+
+    let all_names: Vec<&String> = paths.iter().flatten().collect();
+    let mut nodes: Vec<String> = Vec::with_capacity(all_names.len());
+    for path in &paths {
+        let length = path.len() - 1;
+        for (step, name) in path.iter().enumerate() {
+            let next = if step == length {
+                &path[1]
+            } else {
+                &path[step + 1]
+            };
+            let decoy = *rng.pick(&all_names);
+            let children = if turns[step % directions] == 0 {
+                [next, decoy]
+            } else {
+                [decoy, next]
+            };
+            nodes.push(format!("{name} = ({}, {})", children[0], children[1]));
+        }
+    }
+    rng.shuffle(&mut nodes);
+
+    let turns: String = turns
+        .iter()
+        .map(|&t| if t == 0 { 'L' } else { 'R' })
+        .collect();
+
+    format!("{turns}\n\n{}\n", nodes.join("\n"))
+}
+
+/// Day 9: histories of `values` samples from polynomials of degree up to `max_degree`.
+pub fn oasis_report(seed: u64, histories: usize, values: usize, max_degree: u32) -> String {
+    let mut rng = Rng::new(seed);
+    let mut out = String::new();
+
+    for _ in 0..histories {
+        let degree = rng.range(0..=max_degree as u64) as usize;
+        let coefficients: Vec<i64> = (0..=degree).map(|_| rng.range(0..=10) as i64 - 5).collect();
+        let shift = rng.range(0..=10) as i64 - 5;
+
+        let history: Vec<String> = (0..values as i64)
+            .map(|x| {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, c| acc * (x + shift) + c)
+                    .to_string()
+            })
+            .collect();
+        writeln!(out, "{}", history.join(" ")).unwrap();
+    }
+
+    out
+}
+
+/// Day 12: condition records of up to `max_len` springs, every one with at
+/// least one damaged spring and at least one valid arrangement.
+pub fn spring_rows(seed: u64, rows: usize, max_len: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut out = String::new();
+
+    for _ in 0..rows {
+        let len = rng.range(1..=max_len as u64) as usize;
+        let mut springs: Vec<u8> = (0..len)
+            .map(|_| if rng.chance(1, 2) { b'#' } else { b'.' })
+            .collect();
+        let damaged = rng.index(len);
+        springs[damaged] = b'#';
+
+        let groups: Vec<String> = springs
+            .split(|&spring| spring == b'.')
+            .filter(|group| !group.is_empty())
+            .map(|group| group.len().to_string())
+            .collect();
+
+        for spring in springs.iter_mut() {
+            if rng.chance(1, 2) {
+                *spring = b'?';
+            }
+        }
+        writeln!(
+            out,
+            "{} {}",
+            String::from_utf8(springs).unwrap(),
+            groups.join(",")
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_input() {
+        assert_eq!(node_network(7, 5, 3, 4), node_network(7, 5, 3, 4));
+        assert_eq!(almanac(7, 3, 4, 500), almanac(7, 3, 4, 500));
+        assert_ne!(camel_hands(1, 20, 100), camel_hands(2, 20, 100));
+    }
+
+    #[test]
+    fn test_calibration_document() {
+        let input = calibration_document(1, 200, 4);
+        assert!(input.lines().all(|l| l.chars().any(|c| c.is_ascii_digit())));
+        assert_eq!(
+            crate::day1::part1_bad(&input),
+            crate::day1::part1_maybe(&input)
+        );
+    }
+
+    #[test]
+    fn test_scratchcards_stay_on_table() {
+        let input = scratchcards(3, 6, 5, 8);
+        assert_eq!(
+            crate::day4::part2_recurrence(&input),
+            crate::day4::part2_iterative(&input)
+        );
+    }
+
+    #[test]
+    fn test_almanac_is_bijection() {
+        let input = almanac(5, 2, 6, 1000);
+        assert_eq!(
+            crate::day5::part_2_brute_force(&input),
+            crate::day5::part_2_brute_force_2(&input)
+        );
+    }
+}
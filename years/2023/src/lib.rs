@@ -0,0 +1,40 @@
+extern crate aoc_runner;
+
+#[macro_use]
+extern crate aoc_runner_derive;
+
+mod day1;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day2;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+pub mod generators;
+mod registry;
+pub mod streaming;
+pub mod visualize;
+
+pub use day15::hash;
+pub use registry::{REGISTRY, SOLUTIONS};
+
+aoc_lib! { year = 2023 }
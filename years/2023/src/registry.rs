@@ -0,0 +1,137 @@
+//! Every `#[aoc]` solution of the year, collected from the day modules by
+//! `build.rs`.
+
+use aoc_common::runner::Year;
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+pub static REGISTRY: Year = Year {
+    year: 2023,
+    solutions: SOLUTIONS,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::execution::Execution;
+    use aoc_common::runner::{run, sha256_hex, Filter};
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    static DAY7_SAMPLE: &str = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n";
+
+    fn input_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-runner-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_report_records_answers_and_input() {
+        let dir = input_dir("answers");
+        fs::write(dir.join("day7.txt"), DAY7_SAMPLE).unwrap();
+
+        let filter = Filter {
+            day: Some(7),
+            ..Filter::default()
+        };
+        let report = run(&REGISTRY, &filter, &dir, Execution::Parallel);
+
+        assert_eq!(report.runs.len(), 2);
+        assert_eq!(report.runs[0].answer.as_deref(), Some("6440"));
+        assert_eq!(report.runs[1].answer.as_deref(), Some("5905"));
+        assert_eq!(
+            report.runs[0].input_sha256,
+            Some(sha256_hex(DAY7_SAMPLE.as_bytes()))
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["runs"][1]["part"], 2);
+        assert_eq!(json["runs"][1]["error"], serde_json::Value::Null);
+
+        assert_eq!(report.answer(7, 2), Ok("5905"));
+        assert!(report.answer(8, 1).is_err());
+        assert_eq!(json["runs"][0]["parse_ns"], serde_json::Value::Null);
+        // Day 7 streams its lines, day 10 has a parse step of its own
+        let parse_steps = |day| {
+            SOLUTIONS
+                .iter()
+                .filter(move |s| s.day == day)
+                .map(|s| s.parse.is_some())
+        };
+        assert!(parse_steps(7).all(|parse| !parse));
+        assert!(parse_steps(10).all(|parse| parse));
+    }
+
+    #[test]
+    fn test_report_records_errors() {
+        let dir = input_dir("errors");
+        fs::write(dir.join("day7.txt"), "32T3X 765").unwrap();
+
+        let filter = Filter {
+            day: Some(7),
+            part: Some(1),
+            ..Filter::default()
+        };
+        let report = run(&REGISTRY, &filter, &dir, Execution::Parallel);
+        assert!(report.runs[0].answer.is_none());
+        assert!(report.runs[0]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("InvalidChar"));
+
+        let filter = Filter {
+            day: Some(3),
+            ..Filter::default()
+        };
+        let report = run(&REGISTRY, &filter, &dir, Execution::Parallel);
+        assert!(report.runs[0].input_sha256.is_none());
+        assert!(report.runs[0]
+            .error
+            .as_ref()
+            .unwrap()
+            .ends_with("is missing, pass --fetch with a session to download it"));
+    }
+
+    #[test]
+    fn test_execution_modes_agree() {
+        let dir = input_dir("modes");
+        fs::write(dir.join("day7.txt"), DAY7_SAMPLE).unwrap();
+        fs::write(
+            dir.join("day9.txt"),
+            "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45",
+        )
+        .unwrap();
+
+        let answers = |execution| {
+            let filter = Filter::default();
+            run(&REGISTRY, &filter, &dir, execution)
+                .runs
+                .into_iter()
+                .map(|run| run.answer)
+                .collect::<Vec<_>>()
+        };
+        let parallel = answers(Execution::Parallel);
+        assert_eq!(answers(Execution::Threads(2)), parallel);
+        assert_eq!(answers(Execution::Sequential), parallel);
+
+        let report = run(&REGISTRY, &Filter::default(), &dir, Execution::Sequential);
+        assert_eq!(report.threads, 1);
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["execution"], "sequential");
+    }
+
+    #[test]
+    fn test_filter_by_variant() {
+        let filter = Filter {
+            variant: Some("Equation".to_string()),
+            ..Filter::default()
+        };
+        let matching: Vec<_> = SOLUTIONS.iter().filter(|s| filter.matches(s)).collect();
+        assert_eq!(matching.len(), 1);
+        assert_eq!((matching[0].day, matching[0].part), (6, 2));
+        assert_eq!(filter.days(&REGISTRY), vec![6]);
+    }
+}
@@ -0,0 +1,10 @@
+//! Line-by-line entry points for the line oriented days, for inputs too large
+//! to hold in memory. The `&str` solvers are thin wrappers around these.
+
+pub use crate::day1::{part1_stream as day1_part1, part2_stream as day1_part2};
+pub use crate::day2::{part1_stream as day2_part1, part2_stream as day2_part2};
+pub use crate::day4::{part1_stream as day4_part1, part2_stream as day4_part2};
+pub use crate::day7::{part_1_stream as day7_part1, part_2_stream as day7_part2};
+pub use crate::day9::{
+    extrapolate_backwards_stream as day9_part2, extrapolate_stream as day9_part1,
+};
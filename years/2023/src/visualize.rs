@@ -0,0 +1,10 @@
+//! Text renderings of the puzzles' intermediate state, for looking at what a
+//! solver actually did.
+
+pub use crate::day10::render as day10_loop;
+pub use crate::day13::report as day13_reflections;
+pub use crate::day15::{trace as day15_boxes, Boxes as Day15Boxes, Step as Day15Step};
+pub use crate::day16::render as day16_energized;
+pub use crate::day20::{to_dot as day20_dot, trace as day20_presses};
+pub use crate::day22::{support_graph as day22_support_graph, SupportGraph as Day22SupportGraph};
+pub use crate::day23::render as day23_junctions;